@@ -0,0 +1,18 @@
+//! Runs the mock HowLongToBeat server used by the integration tests, for
+//! trying the CLI offline:
+//!
+//! ```bash
+//! cargo run --example mock_server
+//! cargo run -- witcher --base-url http://127.0.0.1:<port>
+//! ```
+
+#[path = "../tests/support/mock_server.rs"]
+mod mock_server;
+
+fn main() {
+    let server = mock_server::MockServer::start();
+    println!("Mock HowLongToBeat server listening on {}", server.base_url);
+    loop {
+        std::thread::park();
+    }
+}
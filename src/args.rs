@@ -1,7 +1,14 @@
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use clap_num::number_range;
-use strum_macros::{Display, EnumString};
-use crate::post_body::Range;
+use crate::cli_error::{CliError, EXIT_CODES_HELP};
+use crate::duration_formatter::TimeFormat;
+use crate::locale::Lang;
+use crate::pace::{parse_pace, Pace};
+use crate::tabular::{Column, Delimited, DEFAULT_COLUMNS};
+use crate::template::{Template, TEMPLATE_HELP};
+use howlongtobeat::{CacheMode, DlcFilter, Flow, Genre, HltbClient, HltbClientBuilder, Perspective, Platform, Range, RetryPolicy, SearchQuery, SortCategory, DEFAULT_BASE_URL, DEFAULT_RATE_LIMIT, DEFAULT_USER_AGENT};
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(ValueEnum, Clone, PartialEq, Copy)]
 pub(crate) enum ToggleOption {
@@ -9,215 +16,34 @@ pub(crate) enum ToggleOption {
     Never
 }
 
-#[derive(ValueEnum, Clone, PartialEq, EnumString, Display)]
-pub(crate) enum SortCategory {
-    #[strum(serialize = "name")]
-    Name,
-    #[strum(serialize = "main")]
-    Main,
-    #[strum(serialize = "mainp")]
-    #[value(aliases = &["extras", "extra"])]
-    MainExtras,
-    #[strum(serialize = "comp")]
-    #[value(aliases = &["comp", "completion"])]
-    Completionist,
-    #[strum(serialize = "averagea")]
-    #[value(alias = "time")]
-    AverageTime,
-    #[strum(serialize = "rating")]
-    #[value(alias = "rating")]
-    TopRated,
-    #[strum(serialize = "popular")]
-    #[value(aliases = &["popular", "popularity"])]
-    MostPopular,
-    #[strum(serialize = "backlog")]
-    #[value(aliases = &["backlog", "backlogs"])]
-    MostBacklogs,
-    #[strum(serialize = "usersp")]
-    #[value(aliases = &["submissions", "beat"])]
-    MostSubmissions,
-    #[strum(serialize = "playing")]
-    #[value(aliases = &["playing", "played"])]
-    MostPlayed,
-    #[strum(serialize = "speedruns")]
-    #[value(alias = "speedrun")]
-    MostSpeedruns,
-    #[strum(serialize = "reviews")]
-    #[value(alias = "reviews")]
-    MostReviews,
-    #[strum(serialize = "release")]
-    #[value(alias = "release")]
-    ReleaseDate
-}
-
-#[derive(ValueEnum, Clone, EnumString, Display)]
-pub(crate) enum Platform {
-    #[strum(serialize = "")]
-    All,
-    #[strum(serialize = "Emulated")]
-    Emulated,
-    #[strum(serialize = "Nintendo 3DS")]
-    #[value(name = "nintendo-3ds", aliases = &["nintendo 3ds", "nintendo3ds", "3ds"])]
-    Nintendo3DS,
-    #[strum(serialize = "Nintendo Switch")]
-    #[value(aliases = &["nintendo switch", "switch"])]
-    NintendoSwitch,
-    #[value(alias = "desktop")]
-    PC,
-    #[strum(serialize = "PlayStation 3")]
-    #[value(name = "playstation3", aliases = &["playstation 3", "ps3", "ps 3"])]
-    PlayStation3,
-    #[strum(serialize = "PlayStation 4")]
-    #[value(name = "playstation4", aliases = &["playstation 4", "ps4", "ps 4"])]
-    PlayStation4,
-    #[strum(serialize = "PlayStation 5")]
-    #[value(name = "playstation5", aliases = &["playstation 5", "ps5", "ps 5"])]
-    PlayStation5,
-    #[strum(serialize = "PlayStation Now")]
-    #[value(name = "playstation-now", aliases = &["playstation now", "playstationnow", "psnow", "ps now"])]
-    PlayStationNow,
-    #[strum(serialize = "Wii U")]
-    #[value(aliases = &["wii u", "wiiu"])]
-    WiiU,
-    #[strum(serialize = "Xbox 360")]
-    #[value(aliases = &["xbox 360", "xbox-360", "x360", "360"])]
-    Xbox360,
-    #[strum(serialize = "Xbox One")]
-    #[value(aliases = &["xbox one", "xboxone", "xone", "one"])]
-    XboxOne,
-    #[strum(serialize = "Xbox Series X/S")]
-    #[value(aliases = &["xbox xs", "xbox-xs", "xboxxs", "xs"])]
-    XboxSeriesXS
-}
-
-#[derive(ValueEnum, Clone, EnumString, Display, Debug)]
-pub(crate) enum Perspective {
-    #[strum(serialize = "")]
-    All,
-    #[strum(serialize = "First-Person")]
-    #[value(aliases = &["firstperson", "first person", "pov"])]
-    FirstPerson,
-    Isometric,
-    Side,
+#[derive(ValueEnum, Clone, PartialEq, Copy)]
+pub(crate) enum OutputFormat {
     Text,
-    #[strum(serialize = "Third-Person")]
-    #[value(aliases = &["thirdperson", "third person"])]
-    ThirdPerson,
-    #[strum(serialize = "Top-Down")]
-    #[value(aliases = &["topdown", "top down"])]
-    TopDown,
-    #[strum(serialize = "Virtual Reality")]
-    #[value(aliases = &["vr", "virtual-reality", "virtualreality"])]
-    VirtualReality
+    Json,
+    /// One JSON object per game and line, written as soon as each page arrives.
+    Ndjson,
+    /// A GitHub-flavored Markdown table.
+    Markdown,
+    /// A self-contained HTML page.
+    Html,
+    /// The responses exactly as HowLongToBeat sent them, without a stable schema.
+    Raw,
+    Csv,
+    Tsv,
 }
 
-#[derive(ValueEnum, Clone, PartialEq, EnumString, Display)]
-pub(crate) enum Flow {
-    #[strum(serialize = "")]
-    All,
-    Incremental,
-    #[strum(serialize = "Massively Multiplayer")]
-    #[value(aliases = &["massivelymultiplayer", "massively multiplayer"])]
-    MassivelyMultiplayer,
-    Multidirectional,
-    #[strum(serialize = "On-Rails")]
-    #[value(aliases = &["onrails", "on rails"])]
-    OnRails,
-    #[strum(serialize = "Point-and-Click")]
-    #[value(aliases = &["pointandclick", "point and click"])]
-    PointAndClick,
-    #[strum(serialize = "Real-Time")]
-    #[value(aliases = &["realtime", "real time", "rt"])]
-    RealTime,
-    Scrolling,
-    #[strum(serialize = "Turn-Based")]
-    #[value(aliases = &["turnbased", "turn based", "turn"])]
-    TurnBased
-}
-
-#[derive(ValueEnum, Clone, EnumString, Display)]
-pub(crate) enum Genre {
-    #[strum(serialize = "")]
-    All,
-    Action,
-    Adventure,
-    Arcade,
-    #[strum(serialize = "Battle Arena")]
-    #[value(aliases = &["battle-arena", "battle arena"])]
-    BattleArena,
-    #[strum(serialize = "Beat em Up")]
-    #[value(aliases = &["beat-em-up", "beat em up"])]
-    BeatEmUp,
-    #[strum(serialize = "Board Game")]
-    #[value(aliases = &["board-game", "board game"])]
-    BoardGame,
-    Breakout,
-    #[strum(serialize = "Card Game")]
-    #[value(aliases = &["card-game", "card game"])]
-    CardGame,
-    #[strum(serialize = "City-Building")]
-    #[value(aliases = &["city-building", "city building"])]
-    CityBuilding,
-    Compilation,
-    Educational,
-    Fighting,
-    Fitness,
-    Flight,
-    #[strum(serialize = "Full Motion Video (FMV)")]
-    #[value(aliases = &["full-motion-video", "full motion video", "fmv"])]
-    FullMotionVideo,
-    #[strum(serialize = "Hack and Slash")]
-    #[value(aliases = &["hack-and-slash", "hack and slash"])]
-    HackAndSlash,
-    #[strum(serialize = "Hidden Object")]
-    #[value(aliases = &["hidden-object", "hidden object"])]
-    HiddenObject,
-    Horror,
-    #[strum(serialize = "Interactive Art")]
-    #[value(aliases = &["interactive-art", "interactive art"])]
-    InteractiveArt,
-    Management,
-    #[strum(serialize = "Music/Rhythm")]
-    #[value(aliases = &["music/rhythm", "music-rhythm", "music rhythm", "music", "rhythm"])]
-    MusicRhythm,
-    #[strum(serialize = "Open World")]
-    #[value(aliases = &["open-world", "open world"])]
-    OpenWorld,
-    Party,
-    Pinball,
-    Platform,
-    Puzzle,
-    #[strum(serialize = "Racing/Driving")]
-    #[value(aliases = &["racing/driving", "racing-driving", "racing driving", "racing", "driving"])]
-    RacingDriving,
-    Roguelike,
-    #[strum(serialize = "Role-Playing")]
-    #[value(aliases = &["role-playing", "role playing", "rpg"])]
-    RolePlaying,
-    Sandbox,
-    Shooter,
-    Simulation,
-    Social,
-    Sports,
-    Stealth,
-    #[strum(serialize = "Strategy/Tactical")]
-    #[value(aliases = &["strategy/tactical", "strategy-tactical", "strategy tactical", "strategy", "tactical"])]
-    StrategyTactical,
-    Survival,
-    #[strum(serialize = "Tower Defense")]
-    #[value(aliases = &["tower-defense", "tower defense", "td"])]
-    TowerDefense,
-    Trivia,
-    #[strum(serialize = "Vehicular Combat")]
-    #[value(aliases = &["vehicular-combat", "vehicular combat"])]
-    VehicularCombat,
-    #[strum(serialize = "Visual Novel")]
-    #[value(aliases = &["visual-novel", "visual novel"])]
-    VisualNovel
+#[derive(ValueEnum, Clone, PartialEq, Copy)]
+pub(crate) enum Layout {
+    /// A block of lines per game, next to its cover with --images.
+    Cards,
+    /// A row per game with aligned columns, fitted to the terminal width.
+    Table,
+    /// A line per game, like `The Witcher 3 — 51.5h / 103.5h / 173h`, for status bars and notifications.
+    Compact,
 }
 
 fn parse_range(s: &str, min: u16, max: u16) -> Result<Range, String> {
-    if s == "" { return Ok(Range { min: None, max: None }) }
+    if s.is_empty() { return Ok(Range { min: None, max: None }) }
 
     let parts = s.split('-').collect::<Vec<&str>>();
     let mut split: Vec<u16> = Vec::new();
@@ -247,17 +73,148 @@ fn parse_range_time(s: &str) -> Result<Range, String> {
     parse_range(s, u16::MIN, u16::MAX)
 }
 
+fn parse_header(s: &str) -> Result<(String, String), String> {
+    match s.split_once(':') {
+        Some((name, value)) => Ok((name.trim().to_string(), value.trim().to_string())),
+        None => Err("expected \"Name: value\"".to_string())
+    }
+}
+
 fn parse_range_year(s: &str) -> Result<Range, String> {
     parse_range(s, 1958, 2024)
 }
 
-#[derive(Parser)]
-pub(crate) struct Args {
-    pub(crate) search: Vec<String>,
-    #[clap(short, long, default_value_t = 5, help = "Number of results to display")]
-    pub(crate) size: u8,
-    #[clap(short, long, default_value_t = ToggleOption::Always, value_enum, ignore_case = true, help = "Colorize output")]
+#[derive(Subcommand)]
+pub(crate) enum Command {
+    /// Search for games (the default when no command is given)
+    Search(SearchCommand),
+    /// Show everything the game page knows about a single game
+    Game(GameArgs),
+    /// Compare the completion times of several games side by side
+    Compare(CompareArgs),
+    /// Run one search per line of a file (or stdin)
+    Batch(BatchArgs),
+    /// Inspect or clear the local cache
+    Cache(CacheArgs),
+    /// Inspect the settings from the config file
+    Config(ConfigArgs),
+}
+
+// Options shared by every command.
+#[derive(clap::Args)]
+pub(crate) struct GlobalArgs {
+    #[clap(short, long, default_value_t = ToggleOption::Always, value_enum, ignore_case = true, global = true, help = "Colorize output")]
     pub(crate) color: ToggleOption,
+    #[clap(short = 'I', long, default_value_t = false, global = true, help = "Show images (using \"viuer\" crate, full resolution images are displayed only in some terminals)")]
+    pub(crate) images: bool,
+    #[clap(long, global = true, help = "Output JSON with a stable schema, same as --format json")]
+    pub(crate) json: bool,
+    #[clap(long, conflicts_with = "json", global = true, help = "Output the JSON HowLongToBeat responded with, same as --format raw")]
+    pub(crate) raw: bool,
+    #[clap(long, global = true, help = "Print the JSON Schema of the command's --json output and exit")]
+    pub(crate) json_schema: bool,
+    #[clap(long, default_value_t = OutputFormat::Text, value_enum, ignore_case = true, global = true, help = "Output format")]
+    pub(crate) format: OutputFormat,
+    #[clap(long, value_delimiter = ',', default_value = DEFAULT_COLUMNS, value_enum, global = true, help = "Columns to output with --format csv or tsv, comma-separated")]
+    pub(crate) columns: Vec<Column>,
+    #[clap(long, global = true, help = "Print each game using a template, example: '{name}\\t{main:hours}\\t{rating}%'", long_help = TEMPLATE_HELP)]
+    pub(crate) template: Option<String>,
+    #[clap(long, conflicts_with = "template", global = true, help = "Read the --template from a file")]
+    pub(crate) template_file: Option<PathBuf>,
+    #[clap(short, long, conflicts_with = "images", global = true, help = "Write output to a file instead of stdout, without colors")]
+    pub(crate) output: Option<PathBuf>,
+    #[clap(long, value_enum, ignore_case = true, global = true, help = "How to write times, in every output format except JSON [default: short]")]
+    pub(crate) time_format: Option<TimeFormat>,
+    #[clap(long, default_value_t = 1, global = true, help = "Decimals to show with --time-format decimal")]
+    pub(crate) time_precision: usize,
+    #[clap(long, value_enum, ignore_case = true, global = true, help = "Language of labels and messages [default: from LANG]")]
+    pub(crate) lang: Option<Lang>,
+    #[clap(long, env = "HLTB_CONFIG", global = true, help = "Config file with defaults for any option [default: ~/.config/howlongtobeat/config.toml]")]
+    pub(crate) config: Option<PathBuf>,
+    #[clap(long, env = "HLTB_PROFILE", global = true, help = "Apply a named profile from the config file, example: switch-rpgs")]
+    pub(crate) profile: Option<String>,
+    #[clap(short, long, global = true, help = "Print diagnostic information to stderr")]
+    pub(crate) verbose: bool,
+    #[clap(long, env = "HLTB_BASE_URL", default_value = DEFAULT_BASE_URL, global = true, help = "HowLongToBeat origin to send requests to")]
+    pub(crate) base_url: String,
+    #[clap(long, env = "HLTB_PROXY", global = true, help = "HTTP, HTTPS or SOCKS5 proxy URL, example: socks5://localhost:1080")]
+    pub(crate) proxy: Option<String>,
+    #[clap(long, env = "HLTB_CA_BUNDLE", global = true, help = "PEM file with additional trusted CA certificates")]
+    pub(crate) ca_bundle: Option<PathBuf>,
+    #[clap(long, env = "HLTB_TIMEOUT", default_value_t = 30, global = true, help = "Request timeout in seconds")]
+    pub(crate) timeout: u64,
+    #[clap(long, env = "HLTB_CONNECT_TIMEOUT", default_value_t = 10, global = true, help = "Connection timeout in seconds")]
+    pub(crate) connect_timeout: u64,
+    #[clap(long, env = "HLTB_USER_AGENT", default_value = DEFAULT_USER_AGENT, global = true, help = "User-Agent header to send")]
+    pub(crate) user_agent: String,
+    #[clap(short = 'H', long = "header", value_parser = parse_header, global = true, help = "Extra request header, example: \"X-Token: abc\" (can be repeated)")]
+    pub(crate) headers: Vec<(String, String)>,
+    #[clap(long, env = "HLTB_RETRIES", default_value_t = 3, global = true, help = "How many times to retry requests that failed with 429, a server error or a timeout")]
+    pub(crate) retries: u32,
+    #[clap(long, env = "HLTB_RATE_LIMIT", default_value_t = DEFAULT_RATE_LIMIT, global = true, help = "Maximum requests per second, 0 to disable")]
+    pub(crate) rate_limit: f64,
+    #[clap(long, global = true, help = "Ignore cached responses and fetch fresh ones")]
+    pub(crate) refresh: bool,
+    #[clap(long, env = "HLTB_OFFLINE", conflicts_with = "refresh", global = true, help = "Answer only from cached responses, without using the network")]
+    pub(crate) offline: bool,
+    #[clap(long, env = "HLTB_CACHE_TTL", global = true, help = "How long cached responses stay fresh, in seconds [default: 86400]")]
+    pub(crate) cache_ttl: Option<u64>,
+}
+
+#[derive(clap::Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub(crate) struct SearchCommand {
+    #[command(subcommand)]
+    pub(crate) saved: Option<SavedSearchAction>,
+    #[command(flatten)]
+    pub(crate) args: SearchArgs,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum SavedSearchAction {
+    /// Save the words and filters of a search under a name
+    Save(SavedSearchArgs),
+    /// Run a saved search, filters given here override the saved ones
+    Run(SavedSearchArgs),
+    /// List the saved searches
+    List,
+    /// Delete a saved search
+    Delete(SavedSearchName),
+}
+
+#[derive(clap::Args)]
+pub(crate) struct SavedSearchArgs {
+    #[clap(help = "Name of the saved search, example: short-ps5-roguelikes")]
+    pub(crate) name: String,
+    #[command(flatten)]
+    pub(crate) search: SearchArgs,
+}
+
+#[derive(clap::Args)]
+pub(crate) struct SavedSearchName {
+    #[clap(help = "Name of the saved search")]
+    pub(crate) name: String,
+}
+
+#[derive(clap::Args)]
+pub(crate) struct SearchArgs {
+    #[clap(help = "Words to search for")]
+    pub(crate) search: Vec<String>,
+    #[command(flatten)]
+    pub(crate) flags: SearchFlags,
+}
+
+// Search filters and display options, shared by `search` and `batch`.
+#[derive(clap::Args)]
+pub(crate) struct SearchFlags {
+    #[clap(short, long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..), help = "Number of results per page")]
+    pub(crate) size: u32,
+    #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..), help = "Page of results to display")]
+    pub(crate) page: u32,
+    #[clap(long, help = "Fetch every page of results, starting at --page")]
+    pub(crate) all: bool,
+    #[clap(long, default_value_t = 100, requires = "all", help = "Maximum number of games to fetch with --all")]
+    pub(crate) limit: usize,
     #[clap(short = 'S', long, default_value_t = SortCategory::MostPopular, value_enum, ignore_case = true, help = "Sort by category")]
     pub(crate) sort: SortCategory,
     #[clap(short, long, default_value_t = false, help = "Reverse sort order")]
@@ -278,10 +235,161 @@ pub(crate) struct Args {
     pub(crate) dlc: bool,
     #[clap(long, alias = "nodlc", conflicts_with = "dlc", help = "Hide all DLCs")]
     pub(crate) no_dlc: bool,
-    #[clap(short = 'I', long, default_value_t = false, help = "Show images (using \"viuer\" crate, full resolution images are displayed only in some terminals)")]
-    pub(crate) images: bool,
-    #[clap(long, alias = "raw", help = "Output raw JSON")]
-    pub(crate) json: bool,
     #[clap(short, long, help = "Show additional information")]
-    pub(crate) info: bool
+    pub(crate) info: bool,
+    #[clap(long, help = "Hide times fewer people submitted, and games left without any")]
+    pub(crate) min_submissions: Option<u32>,
+    #[clap(long, value_parser = parse_pace, help = "Estimate how long finishing each game takes at a pace, example: 2h/day or 10h/week")]
+    pub(crate) pace: Option<Pace>,
+    #[clap(long, default_value_t = Layout::Cards, value_enum, ignore_case = true, help = "How to lay out text results")]
+    pub(crate) layout: Layout,
+    #[clap(long, help = "Exit with code 8 when no games are found")]
+    pub(crate) fail_if_empty: bool
+}
+
+#[derive(clap::Args)]
+pub(crate) struct GameArgs {
+    #[clap(required_unless_present = "json_schema", help = "Game id or name")]
+    pub(crate) game: Vec<String>,
+}
+
+#[derive(clap::Args)]
+pub(crate) struct CompareArgs {
+    #[clap(required_unless_present = "json_schema", num_args = 2.., help = "Game ids or names, quote names with spaces")]
+    pub(crate) games: Vec<String>,
+}
+
+#[derive(clap::Args)]
+pub(crate) struct BatchArgs {
+    #[clap(default_value = "-", help = "File with one search per line, \"-\" for stdin")]
+    pub(crate) file: PathBuf,
+    #[command(flatten)]
+    pub(crate) flags: SearchFlags,
+}
+
+#[derive(clap::Args)]
+pub(crate) struct CacheArgs {
+    #[command(subcommand)]
+    pub(crate) action: CacheAction,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum CacheAction {
+    /// Print where the cache is stored and what it contains
+    Show,
+    /// Delete all cached data
+    Clear,
+}
+
+#[derive(clap::Args)]
+pub(crate) struct ConfigArgs {
+    #[command(subcommand)]
+    pub(crate) action: ConfigAction,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum ConfigAction {
+    /// Print every option with the value it gets from the command line, config file or default
+    Show,
+}
+
+#[derive(Parser)]
+#[command(after_help = EXIT_CODES_HELP)]
+pub(crate) struct Cli {
+    #[command(flatten)]
+    pub(crate) global: GlobalArgs,
+    #[command(subcommand)]
+    pub(crate) command: Option<Command>,
+    #[command(flatten)]
+    pub(crate) search: SearchArgs,
+}
+
+impl Cli {
+    /// The command to run, searching with the top-level arguments when none is given.
+    pub(crate) fn into_command(self) -> (GlobalArgs, Command) {
+        (self.global, self.command.unwrap_or(Command::Search(SearchCommand { saved: None, args: self.search })))
+    }
+}
+
+impl GlobalArgs {
+    pub(crate) fn to_client_builder(&self) -> HltbClientBuilder {
+        let mut builder = HltbClient::builder()
+            .base_url(&self.base_url)
+            .user_agent(&self.user_agent)
+            .timeout(Duration::from_secs(self.timeout))
+            .connect_timeout(Duration::from_secs(self.connect_timeout))
+            .retry_policy(RetryPolicy { max_retries: self.retries, ..Default::default() })
+            .rate_limit(self.rate_limit);
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(ca_bundle) = &self.ca_bundle {
+            builder = builder.ca_bundle(ca_bundle);
+        }
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        builder
+    }
+
+    /// The template given with --template or --template-file, if any.
+    pub(crate) fn template(&self) -> Result<Option<Template>, CliError> {
+        if let Some(template) = &self.template {
+            return Template::parse_argument(template).map(Some);
+        }
+        let Some(path) = &self.template_file else { return Ok(None) };
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| CliError::Input(format!("{}: {}", path.display(), e)))?;
+        // a template file usually ends with a new line, which is already added after each game
+        let contents = contents.strip_suffix('\n').unwrap_or(&contents);
+        Template::parse(contents).map(Some)
+    }
+
+    pub(crate) fn format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else if self.raw {
+            OutputFormat::Raw
+        } else {
+            self.format
+        }
+    }
+
+    /// The CSV/TSV writer when one of those formats was requested.
+    pub(crate) fn delimited(&self) -> Option<Delimited> {
+        match self.format() {
+            OutputFormat::Csv => Some(Delimited::Csv),
+            OutputFormat::Tsv => Some(Delimited::Tsv),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn cache_mode(&self) -> CacheMode {
+        if self.offline {
+            CacheMode::Offline
+        } else if self.refresh {
+            CacheMode::Refresh
+        } else {
+            CacheMode::Normal
+        }
+    }
+}
+
+impl SearchFlags {
+    pub(crate) fn to_query<S: AsRef<str>>(&self, terms: &[S]) -> SearchQuery {
+        SearchQuery {
+            page: self.page,
+            size: self.size,
+            sort: self.sort,
+            reverse: self.reverse,
+            platform: self.platform,
+            perspective: self.perspective,
+            flow: self.flow,
+            genre: self.genre,
+            range_time: self.range_time.clone(),
+            range_year: self.range_year.clone(),
+            dlc: if self.dlc { DlcFilter::Only } else if self.no_dlc { DlcFilter::Hide } else { DlcFilter::Include },
+            ..SearchQuery::new(terms)
+        }
+    }
 }
@@ -0,0 +1,73 @@
+use std::{fmt, io};
+use std::process::ExitCode;
+
+/// Exit codes documented in `--help` and the README.
+pub(crate) const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0  Success
+  2  Invalid arguments, configuration or input file
+  3  Network error
+  4  Search endpoint could not be discovered
+  5  HowLongToBeat responded with an error status
+  6  Unexpected response from HowLongToBeat
+  7  Cover image could not be loaded or displayed
+  8  No games found (searches only with --fail-if-empty)
+  9  Response not cached (with --offline)
+  10 Output could not be written";
+
+#[derive(Debug)]
+pub(crate) enum CliError {
+    Hltb(howlongtobeat::Error),
+    Image(String),
+    Input(String),
+    Config(String),
+    Template(String),
+    Usage(String),
+    Output(io::Error),
+    NoResults,
+}
+
+impl CliError {
+    pub(crate) fn exit_code(&self) -> ExitCode {
+        let code = match self {
+            CliError::Hltb(howlongtobeat::Error::Config(_)) => 2,
+            CliError::Hltb(howlongtobeat::Error::Network(_)) => 3,
+            CliError::Hltb(howlongtobeat::Error::HashDiscovery(_)) => 4,
+            CliError::Hltb(howlongtobeat::Error::Status { .. }) => 5,
+            CliError::Hltb(howlongtobeat::Error::Decode(_) | howlongtobeat::Error::Parse(_)) => 6,
+            CliError::Hltb(howlongtobeat::Error::NotCached(_)) => 9,
+            CliError::Image(_) => 7,
+            CliError::Input(_) | CliError::Config(_) | CliError::Template(_) | CliError::Usage(_) => 2,
+            CliError::NoResults => 8,
+            CliError::Output(_) => 10,
+        };
+        ExitCode::from(code)
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Hltb(e) => write!(f, "{}", e),
+            CliError::Image(reason) => write!(f, "could not display cover image: {}", reason),
+            CliError::Input(reason) => write!(f, "could not read input: {}", reason),
+            CliError::Config(reason) => write!(f, "invalid configuration: {}", reason),
+            CliError::Template(reason) => write!(f, "invalid template: {}", reason),
+            CliError::Usage(reason) => write!(f, "{}", reason),
+            CliError::Output(e) => write!(f, "could not write output: {}", e),
+            CliError::NoResults => write!(f, "no games found"),
+        }
+    }
+}
+
+impl From<howlongtobeat::Error> for CliError {
+    fn from(e: howlongtobeat::Error) -> Self {
+        CliError::Hltb(e)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        CliError::Output(e)
+    }
+}
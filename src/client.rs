@@ -0,0 +1,391 @@
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use futures::stream::{self, Stream, TryStreamExt};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, ClientBuilder, Proxy, RequestBuilder, Response, StatusCode};
+use crate::discovery::{self, Discovery};
+use crate::error::{Error, Result};
+use crate::game_page::{parse_game_page, GameDetails};
+use crate::hash_cache::HashCache;
+use crate::post_result::Game;
+use crate::query::SearchQuery;
+use crate::rate_limit::RateLimiter;
+use crate::response_cache::{CacheMode, ResponseCache};
+use crate::retry::{Retry, RetryPolicy};
+use crate::SearchPage;
+
+pub const DEFAULT_BASE_URL: &str = "https://howlongtobeat.com";
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
+
+fn is_stale_hash_error(error: &Error) -> bool {
+    match error {
+        Error::Status { status, .. } => *status == StatusCode::NOT_FOUND || *status == StatusCode::FORBIDDEN,
+        // the endpoint behind an old hash may answer with anything, like a page of HTML
+        Error::Decode(_) => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn check_status(response: Response) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else {
+        Err(Error::Status { url: response.url().to_string(), status })
+    }
+}
+
+type DiscoveryListener = Arc<dyn Fn(&Discovery) + Send + Sync>;
+type RetryListener = Arc<dyn Fn(&Retry) + Send + Sync>;
+
+/// Requests per second sent by default, see [`HltbClientBuilder::rate_limit`].
+pub const DEFAULT_RATE_LIMIT: f64 = 5.0;
+
+/// Transport settings for an [`HltbClient`].
+#[derive(Clone, Debug)]
+pub struct HltbClientBuilder {
+    base_url: String,
+    user_agent: String,
+    proxy: Option<String>,
+    ca_bundle: Option<PathBuf>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    headers: Vec<(String, String)>,
+    retry_policy: RetryPolicy,
+    rate_limit: f64,
+}
+
+impl HltbClientBuilder {
+    /// Origin of the HowLongToBeat website, useful for pointing at a mirror or a mock server.
+    pub fn base_url(self, base_url: impl Into<String>) -> Self {
+        HltbClientBuilder { base_url: base_url.into().trim_end_matches('/').to_string(), ..self }
+    }
+
+    pub fn user_agent(self, user_agent: impl Into<String>) -> Self {
+        HltbClientBuilder { user_agent: user_agent.into(), ..self }
+    }
+
+    /// Routes all requests through an `http://`, `https://` or `socks5://` proxy.
+    pub fn proxy(self, proxy: impl Into<String>) -> Self {
+        HltbClientBuilder { proxy: Some(proxy.into()), ..self }
+    }
+
+    /// Trusts the PEM certificates in `path` in addition to the system roots.
+    pub fn ca_bundle(self, path: impl Into<PathBuf>) -> Self {
+        HltbClientBuilder { ca_bundle: Some(path.into()), ..self }
+    }
+
+    pub fn timeout(self, timeout: Duration) -> Self {
+        HltbClientBuilder { timeout: Some(timeout), ..self }
+    }
+
+    pub fn connect_timeout(self, timeout: Duration) -> Self {
+        HltbClientBuilder { connect_timeout: Some(timeout), ..self }
+    }
+
+    /// Sends an extra header with every request, overriding the defaults.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        HltbClientBuilder { retry_policy, ..self }
+    }
+
+    /// Maximum number of requests per second, shared by every clone of the client.
+    /// Zero or less disables the limit.
+    pub fn rate_limit(self, per_second: f64) -> Self {
+        HltbClientBuilder { rate_limit: per_second, ..self }
+    }
+
+    pub fn build(self) -> Result<HltbClient> {
+        let mut headers = HeaderMap::new();
+        let defaults = [
+            ("User-Agent".to_string(), self.user_agent.clone()),
+            ("Referer".to_string(), self.base_url.clone()),
+            ("Content-Type".to_string(), "application/json".to_string()),
+        ];
+        for (name, value) in defaults.iter().chain(&self.headers) {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| Error::Config(format!("invalid header name {:?}: {}", name, e)))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| Error::Config(format!("invalid value for header {}: {}", name, e)))?;
+            headers.insert(name, value);
+        }
+
+        let mut builder = ClientBuilder::new().default_headers(headers);
+        if let Some(proxy) = &self.proxy {
+            let proxy = Proxy::all(proxy)
+                .map_err(|e| Error::Config(format!("invalid proxy {:?}: {}", proxy, e)))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(path) = &self.ca_bundle {
+            let pem = std::fs::read(path)
+                .map_err(|e| Error::Config(format!("could not read {}: {}", path.display(), e)))?;
+            let certificates = Certificate::from_pem_bundle(&pem)
+                .map_err(|e| Error::Config(format!("invalid CA bundle {}: {}", path.display(), e)))?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        let rate_limiter = (self.rate_limit > 0.0)
+            .then(|| Arc::new(RateLimiter::new(self.rate_limit, self.rate_limit.ceil() as u32)));
+
+        Ok(HltbClient {
+            http: builder.build()?,
+            base_url: self.base_url,
+            retry_policy: self.retry_policy,
+            rate_limiter,
+            retry_listener: None,
+            hash_cache: HashCache::default(),
+            response_cache: ResponseCache::default(),
+            cache_mode: CacheMode::default(),
+            discovery_listener: None,
+        })
+    }
+}
+
+impl Default for HltbClientBuilder {
+    fn default() -> Self {
+        HltbClientBuilder {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            proxy: None,
+            ca_bundle: None,
+            timeout: None,
+            connect_timeout: None,
+            headers: Vec::new(),
+            retry_policy: RetryPolicy::default(),
+            rate_limit: DEFAULT_RATE_LIMIT,
+        }
+    }
+}
+
+/// Client for the HowLongToBeat website.
+#[derive(Clone)]
+pub struct HltbClient {
+    http: Client,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    retry_listener: Option<RetryListener>,
+    hash_cache: HashCache,
+    response_cache: ResponseCache,
+    cache_mode: CacheMode,
+    discovery_listener: Option<DiscoveryListener>,
+}
+
+impl HltbClient {
+    /// Creates a client with the default settings, see [`HltbClient::builder`] to customize them.
+    pub fn new() -> Result<Self> {
+        Self::builder().build()
+    }
+
+    pub fn builder() -> HltbClientBuilder {
+        HltbClientBuilder::default()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn hash_cache(&self) -> &HashCache {
+        &self.hash_cache
+    }
+
+    /// Replaces the default on-disk cache of the search hash.
+    pub fn with_hash_cache(self, hash_cache: HashCache) -> Self {
+        HltbClient { hash_cache, ..self }
+    }
+
+    pub fn response_cache(&self) -> &ResponseCache {
+        &self.response_cache
+    }
+
+    /// Replaces the default on-disk cache of search responses and game pages.
+    pub fn with_response_cache(self, response_cache: ResponseCache) -> Self {
+        HltbClient { response_cache, ..self }
+    }
+
+    /// Sets whether responses are read from the cache, refreshed, or only read from the cache.
+    pub fn with_cache_mode(self, cache_mode: CacheMode) -> Self {
+        HltbClient { cache_mode, ..self }
+    }
+
+    /// Calls `listener` whenever the search hash had to be discovered.
+    pub fn on_discovery(self, listener: impl Fn(&Discovery) + Send + Sync + 'static) -> Self {
+        HltbClient { discovery_listener: Some(Arc::new(listener)), ..self }
+    }
+
+    /// Calls `listener` before a failed request is retried.
+    pub fn on_retry(self, listener: impl Fn(&Retry) + Send + Sync + 'static) -> Self {
+        HltbClient { retry_listener: Some(Arc::new(listener)), ..self }
+    }
+
+    /// Looks up the current search hash in the HowLongToBeat frontend, bypassing the cache.
+    pub async fn discover(&self) -> Result<Discovery> {
+        let discovery = discovery::discover(self).await?;
+        if let Some(listener) = &self.discovery_listener {
+            listener(&discovery);
+        }
+        Ok(discovery)
+    }
+
+    /// Runs a search and returns the first page of results.
+    pub async fn search(&self, query: &SearchQuery) -> Result<SearchPage> {
+        let body = serde_json::to_string(&query.to_body())?;
+        let key = format!("POST {}/api/search {}", self.base_url, body);
+        let text = self.cached(&key, "this search", self.search_uncached(&body)).await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// Posts the search with the cached hash, or a newly discovered one. A new
+    /// hash is only cached once it answered with a page of results.
+    async fn search_uncached(&self, body: &str) -> Result<String> {
+        if let Some(search_hash) = self.hash_cache.load(&self.base_url) {
+            match self.post_search(&search_hash, body).await {
+                // the hash has most likely changed, discover it again
+                Err(e) if is_stale_hash_error(&e) => self.hash_cache.clear(),
+                result => return result,
+            }
+        }
+
+        let search_hash = self.discover().await?.hash;
+        let result = self.post_search(&search_hash, body).await;
+        if result.is_ok() {
+            self.hash_cache.store(&self.base_url, &search_hash);
+        }
+        result
+    }
+
+    /// Answers from the response cache according to the cache mode, running
+    /// `fetch` and caching its result otherwise. `what` names the request in
+    /// the error returned on an offline miss.
+    async fn cached(&self, key: &str, what: &str, fetch: impl Future<Output = Result<String>>) -> Result<String> {
+        match self.cache_mode {
+            CacheMode::Normal => if let Some(response) = self.response_cache.load(key) {
+                return Ok(response);
+            },
+            CacheMode::Refresh => {}
+            CacheMode::Offline => return self.response_cache.load_any_age(key)
+                .map(|(response, _)| response)
+                .ok_or_else(|| Error::NotCached(what.to_string())),
+        }
+        let response = fetch.await?;
+        self.response_cache.store(key, &response);
+        Ok(response)
+    }
+
+    /// Fetches the pages of a search one after another, starting at `query.page`.
+    /// The next page is only requested once the previous one has been consumed.
+    pub fn search_pages<'a>(&'a self, query: &SearchQuery) -> impl Stream<Item = Result<SearchPage>> + 'a {
+        stream::try_unfold(Some(query.clone()), move |query| async move {
+            let Some(query) = query else { return Ok(None) };
+            let page = self.search(&query).await?;
+            let next = if !page.data.is_empty() && (query.page as i64) < page.page_total {
+                Some(SearchQuery { page: query.page + 1, ..query })
+            } else {
+                None
+            };
+            Ok(Some((page, next)))
+        })
+    }
+
+    /// Streams every game matching the search across all pages, starting at `query.page`.
+    pub fn search_stream<'a>(&'a self, query: &SearchQuery) -> impl Stream<Item = Result<Game>> + 'a {
+        self.search_pages(query)
+            .map_ok(|page| stream::iter(page.data.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Sends the request once the rate limiter allows it, retrying according to the retry policy.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            let Some(this_attempt) = request.try_clone() else { return check_status(request.send().await?) };
+
+            let (url, reason, delay) = match this_attempt.send().await {
+                Ok(response) => match self.retry_policy.delay_after_response(&response, attempt) {
+                    Some(delay) => (response.url().to_string(), response.status().to_string(), delay),
+                    None => return check_status(response),
+                },
+                Err(e) => match self.retry_policy.delay_after_error(&e, attempt) {
+                    Some(delay) => (e.url().map(|url| url.to_string()).unwrap_or_default(), e.to_string(), delay),
+                    None => return Err(e.into()),
+                },
+            };
+
+            attempt += 1;
+            if let Some(listener) = &self.retry_listener {
+                listener(&Retry { url, reason, attempt, delay });
+            }
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    pub(crate) async fn get_text(&self, url: &str) -> Result<String> {
+        Ok(self.send(self.http.get(url)).await?.text().await?)
+    }
+
+    /// Sends a search and returns the raw response, once it decodes as a page of results.
+    pub(crate) async fn post_search(&self, search_hash: &str, body: &str) -> Result<String> {
+        let request = self.http
+            .post(format!("{}/api/search/{}", self.base_url, search_hash))
+            .body(body.to_string());
+        let text = self.send(request).await?.text().await?;
+        serde_json::from_str::<SearchPage>(&text)?;
+        Ok(text)
+    }
+
+    /// Fetches the details shown on the game page.
+    pub async fn game(&self, game_id: i64) -> Result<GameDetails> {
+        let url = format!("{}/game/{}", self.base_url, game_id);
+        // only pages that parse are cached, like search responses
+        let fetch = async {
+            let html = self.get_text(&url).await?;
+            parse_game_page(&html)?;
+            Ok(html)
+        };
+        let html = self.cached(&format!("GET {}", url), &format!("game {}", game_id), fetch).await?;
+        parse_game_page(&html)
+    }
+
+    /// Searches for `name` and returns the id of the best match: an exact name
+    /// match if there is one, otherwise the most popular result.
+    pub async fn find_game_id(&self, name: &str) -> Result<Option<i64>> {
+        let page = self.search(&SearchQuery::new([name])).await?;
+        let exact = page.data.iter().find(|game| game.game_name.eq_ignore_ascii_case(name.trim()));
+        Ok(exact.or(page.data.first()).map(|game| game.game_id))
+    }
+
+    pub fn cover_url(&self, game_image: &str) -> String {
+        format!("{}/games/{}?width=100", self.base_url, game_image)
+    }
+
+    /// Downloads the raw bytes of a game's cover image.
+    pub async fn fetch_cover(&self, game: &Game) -> Result<Vec<u8>> {
+        self.fetch_image(&game.game_image).await
+    }
+
+    /// Downloads the raw bytes of a cover image by its `game_image` file name.
+    pub async fn fetch_image(&self, game_image: &str) -> Result<Vec<u8>> {
+        if self.cache_mode == CacheMode::Offline {
+            return Err(Error::NotCached(format!("cover image {}", game_image)));
+        }
+        let bytes = self.send(self.http.get(self.cover_url(game_image))).await?.bytes().await?;
+        Ok(bytes.to_vec())
+    }
+}
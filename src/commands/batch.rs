@@ -0,0 +1,109 @@
+use std::fs;
+use std::io::{Read, Write};
+use colored::Colorize;
+use howlongtobeat::HltbClient;
+use howlongtobeat::schema::{BatchDocument, BatchSearch, GameSummary, QueriedGame, SearchResults, Versioned};
+use crate::args::{BatchArgs, GlobalArgs, OutputFormat};
+use crate::cli_error::CliError;
+use crate::report::{print_report, ReportRow, ReportSection};
+use crate::commands::search::{fetch_results, render_results, stream_results};
+use crate::render::Output;
+
+fn read_queries(args: &BatchArgs) -> Result<Vec<String>, CliError> {
+    let contents = if args.file.as_os_str() == "-" {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents).map_err(|e| CliError::Input(format!("stdin: {}", e)))?;
+        contents
+    } else {
+        fs::read_to_string(&args.file).map_err(|e| CliError::Input(format!("{}: {}", args.file.display(), e)))?
+    };
+
+    Ok(contents.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Runs every search, reporting failures as they happen and returning the last one.
+pub(crate) async fn run(out: &mut Output, global: &GlobalArgs, args: &BatchArgs, client: &HltbClient) -> Result<(), CliError> {
+    let template = global.template()?;
+    let queries = read_queries(args)?;
+
+    let format = global.format();
+    let delimited = global.delimited();
+    if let Some(delimited) = delimited {
+        writeln!(out, "{}", delimited.header(&["query"], &global.columns))?;
+    }
+
+    let mut results = Vec::new();
+    let mut raw_results = Vec::new();
+    let mut sections = Vec::new();
+    let mut last_error = None;
+    for (index, line) in queries.iter().enumerate() {
+        let query = args.flags.to_query(&[line]);
+
+        if template.is_none() && format == OutputFormat::Ndjson {
+            let streamed = stream_results(client, &query, &args.flags, |game| {
+                let game = QueriedGame { query: line.clone(), game: GameSummary::from_game(game, client.base_url()) };
+                writeln!(out, "{}", serde_json::to_string(&Versioned::new(game)).unwrap())?;
+                Ok(())
+            }).await;
+            match streamed {
+                Err(e @ CliError::Output(_)) => return Err(e),
+                Err(e) => {
+                    eprintln!("{} {}: {}", "error:".red().bold(), line, e);
+                    last_error = Some(e);
+                }
+                Ok(_) => {}
+            }
+            continue;
+        }
+
+        let res = match fetch_results(client, &query, &args.flags).await {
+            Ok(res) => res,
+            Err(e) => {
+                eprintln!("{} {}: {}", "error:".red().bold(), line, e);
+                last_error = Some(e);
+                continue;
+            }
+        };
+
+        if template.is_some() {
+            render_results(out, global, &args.flags, client, &query, template.as_ref(), &res).await?;
+            continue;
+        }
+
+        match (format, delimited) {
+            (OutputFormat::Json, _) => results.push(BatchSearch { query: line.clone(), results: SearchResults::from_page(&res, client.base_url()) }),
+            (OutputFormat::Markdown | OutputFormat::Html, _) => sections.push(ReportSection {
+                title: Some(line.clone()),
+                rows: res.data.iter().map(ReportRow::from_game).collect(),
+            }),
+            (OutputFormat::Raw, _) => raw_results.push(serde_json::json!({ "query": line, "result": res })),
+            (_, Some(delimited)) => for game in &res.data {
+                writeln!(out, "{}", delimited.row(&[line], &global.columns, game))?;
+            },
+            _ => {}
+        }
+        if format != OutputFormat::Text { continue }
+
+        if index > 0 { writeln!(out)? }
+        writeln!(out, "{}", format!("# {}", line).bold())?;
+        render_results(out, global, &args.flags, client, &query, None, &res).await?;
+    }
+
+    if template.is_none() {
+        match format {
+            OutputFormat::Json => writeln!(out, "{}", serde_json::to_string(&Versioned::new(BatchDocument { searches: results })).unwrap())?,
+            OutputFormat::Raw => writeln!(out, "{}", serde_json::to_string(&raw_results).unwrap())?,
+            OutputFormat::Markdown | OutputFormat::Html => print_report(out, format, client, &sections, "HowLongToBeat").await?,
+            _ => {}
+        }
+    }
+
+    match last_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
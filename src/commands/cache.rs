@@ -0,0 +1,45 @@
+use std::io::Write;
+use colored::Colorize;
+use howlongtobeat::cache_file::now;
+use howlongtobeat::HltbClient;
+use crate::args::{CacheAction, CacheArgs};
+use crate::cli_error::CliError;
+use crate::locale::{fill, messages};
+use crate::render::Output;
+
+pub(crate) fn run(out: &mut Output, args: &CacheArgs, client: &HltbClient) -> Result<(), CliError> {
+    let m = messages();
+    let hash_cache = client.hash_cache();
+    match args.action {
+        CacheAction::Show => {
+            let Some(path) = hash_cache.path() else {
+                writeln!(out, "{}", m.caching_disabled)?;
+                return Ok(());
+            };
+            writeln!(out, "{} {}", format!("{}:", m.search_hash_cache).truecolor(200, 200, 200), path.display())?;
+            match hash_cache.entry() {
+                Some(entry) => {
+                    let age = now().saturating_sub(entry.discovered_at);
+                    let state = if age < hash_cache.ttl().as_secs() { m.fresh } else { m.expired };
+                    let minutes = m.plural(&m.minutes, age / 60);
+                    writeln!(out, "  {}", fill(m.hash_entry, &[&entry.hash, &entry.base_url, &minutes, &state]))?;
+                }
+                None => writeln!(out, "  {}", m.empty)?,
+            }
+
+            let response_cache = client.response_cache();
+            if let Some(dir) = response_cache.dir() {
+                writeln!(out, "{} {}", format!("{}:", m.response_cache).truecolor(200, 200, 200), dir.display())?;
+                let responses = m.plural(&m.responses, response_cache.len() as u64);
+                let minutes = m.plural(&m.minutes, response_cache.ttl().as_secs() / 60);
+                writeln!(out, "  {}", fill(m.fresh_for, &[&responses, &minutes]))?;
+            }
+        }
+        CacheAction::Clear => {
+            hash_cache.clear();
+            client.response_cache().clear();
+            writeln!(out, "{}", m.cache_cleared)?;
+        }
+    }
+    Ok(())
+}
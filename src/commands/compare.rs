@@ -0,0 +1,96 @@
+use std::io::Write;
+use std::time::Duration;
+use colored::Colorize;
+use futures::future::try_join_all;
+use howlongtobeat::HltbClient;
+use howlongtobeat::schema::{CompareDocument, GameInfo, Versioned};
+use crate::args::{CompareArgs, GlobalArgs, OutputFormat};
+use crate::cli_error::CliError;
+use crate::render::Output;
+use crate::commands::game::resolve_game_id;
+use crate::display_time_components::format_with_color;
+use crate::duration_formatter::DurationFormatter;
+use crate::locale::messages;
+use crate::report::{print_report, ReportRow, ReportSection};
+use crate::tabular::print_delimited;
+
+fn format_time(time: Duration, count: u32) -> String {
+    if count == 0 { "–".to_string() } else { time.format() }
+}
+
+pub(crate) async fn run(out: &mut Output, global: &GlobalArgs, args: &CompareArgs, client: &HltbClient) -> Result<(), CliError> {
+    let template = global.template()?;
+    let games = try_join_all(args.games.iter().map(|game| async move {
+        let game_id = resolve_game_id(client, game).await?;
+        Ok::<_, CliError>(client.game(game_id).await?)
+    })).await?;
+
+    if let Some(template) = template {
+        for game in &games {
+            writeln!(out, "{}", template.render(game))?;
+        }
+        return Ok(());
+    }
+
+    match global.format() {
+        OutputFormat::Json => {
+            let games = games.iter().map(|game| GameInfo::from_details(game, client.base_url())).collect();
+            writeln!(out, "{}", serde_json::to_string(&Versioned::new(CompareDocument { games })).unwrap())?;
+            return Ok(());
+        }
+        OutputFormat::Ndjson => {
+            for game in &games {
+                writeln!(out, "{}", serde_json::to_string(&Versioned::new(GameInfo::from_details(game, client.base_url()))).unwrap())?;
+            }
+            return Ok(());
+        }
+        format @ (OutputFormat::Markdown | OutputFormat::Html) => {
+            print_report(out, format, client, &[ReportSection { title: None, rows: games.iter().map(ReportRow::from_details).collect() }], "HowLongToBeat").await?;
+            return Ok(());
+        }
+        OutputFormat::Raw => {
+            writeln!(out, "{}", serde_json::to_string(&games).unwrap())?;
+            return Ok(());
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            print_delimited(out, global.delimited().unwrap(), &global.columns, &games)?;
+            return Ok(());
+        }
+        OutputFormat::Text => {}
+    }
+
+    let m = messages();
+    let headers = [m.main_story, m.main_extras, m.completionist, m.rating];
+    let rows: Vec<(String, Vec<(String, u32)>)> = games.iter()
+        .map(|game| (game.game_name.clone(), vec![
+            (format_time(game.comp_main, game.comp_main_count), game.comp_main_count),
+            (format_time(game.comp_plus, game.comp_plus_count), game.comp_plus_count),
+            (format_time(game.comp_100, game.comp_100_count), game.comp_100_count),
+            (if game.review_score == 0 { m.not_rated.to_string() } else { format!("{}%", game.review_score) }, u32::MAX),
+        ]))
+        .collect();
+
+    let name_width = rows.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    let widths: Vec<usize> = headers.iter().enumerate()
+        .map(|(index, header)| rows.iter().map(|(_, columns)| columns[index].0.chars().count()).max().unwrap_or(0).max(header.chars().count()))
+        .collect();
+
+    let header = headers.iter().zip(&widths)
+        .map(|(header, width)| format!("{:>width$}", header))
+        .collect::<Vec<String>>()
+        .join("  ");
+    writeln!(out, "{}", format!("{:name_width$}  {}", "", header).truecolor(200, 200, 200))?;
+
+    for (name, columns) in rows {
+        let columns = columns.iter().zip(&widths)
+            .map(|((value, count), width)| {
+                let value = format!("{:>width$}", value);
+                // the rating isn't a poll, so it isn't colored by accuracy
+                if *count == u32::MAX { value } else { format_with_color(&value, *count) }
+            })
+            .collect::<Vec<String>>()
+            .join("  ");
+        writeln!(out, "{}  {}", format!("{:name_width$}", name).bold(), columns)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::io::Write;
+use colored::Colorize;
+use crate::args::{ConfigAction, ConfigArgs};
+use crate::cli_error::CliError;
+use crate::config::{Layers, Source};
+use crate::locale::{fill, messages};
+use crate::render::Output;
+
+/// Settings longer than this aren't padded to line up the comments.
+const MAX_ALIGNED: usize = 40;
+
+pub(crate) fn run(out: &mut Output, args: &ConfigArgs, layers: &Layers) -> Result<(), CliError> {
+    let m = messages();
+    match args.action {
+        ConfigAction::Show => {
+            let effective = layers.effective()?;
+            let path = effective.path.as_ref().map(|path| path.display().to_string()).unwrap_or_default();
+            let header = match (&effective.profile, effective.loaded) {
+                (Some(profile), _) => fill(m.settings_from_profile, &[&path, profile]),
+                (None, true) => fill(m.settings_from, &[&path]),
+                (None, false) => fill(m.no_config_file, &[&path]),
+            };
+            writeln!(out, "{}", format!("# {}", header).truecolor(120, 120, 120))?;
+
+            let lines: Vec<String> = effective.settings.iter()
+                // options without a value are left commented out, like in a config file
+                .map(|setting| match &setting.value {
+                    Some(value) => format!("{} = {}", setting.name, value),
+                    None => format!("# {} =", setting.name),
+                })
+                .collect();
+            // a long user agent shouldn't push every comment far to the right
+            let width = lines.iter().map(|line| line.chars().count()).filter(|width| *width <= MAX_ALIGNED).max().unwrap_or(0);
+            for (line, setting) in lines.iter().zip(&effective.settings) {
+                let source = match &setting.source {
+                    Source::Default => m.from_default.to_string(),
+                    Source::Config => m.from_config.to_string(),
+                    Source::Profile(name) => fill(m.from_profile, &[name]),
+                    Source::SavedSearch(name) => fill(m.from_saved_search, &[name]),
+                    Source::Environment => m.from_environment.to_string(),
+                    Source::CommandLine => m.from_command_line.to_string(),
+                };
+                let padding = " ".repeat(width.saturating_sub(line.chars().count()));
+                // what differs from the defaults stands out
+                let line = if setting.source == Source::Default { line.normal() } else { line.bold() };
+                writeln!(out, "{}{}  {}", line, padding, format!("# {}", source).truecolor(120, 120, 120))?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use std::io::Write;
+use howlongtobeat::HltbClient;
+use howlongtobeat::schema::{GameDocument, GameInfo, Versioned};
+use crate::args::{GameArgs, GlobalArgs, OutputFormat};
+use crate::cli_error::CliError;
+use crate::render::Output;
+use crate::display_game_details::display_game_details;
+use crate::images::load_image;
+use crate::report::{print_report, ReportRow, ReportSection};
+use crate::tabular::print_delimited;
+
+/// Treats `game` as an id if it is numeric, otherwise looks it up by name.
+pub(crate) async fn resolve_game_id(client: &HltbClient, game: &str) -> Result<i64, CliError> {
+    match game.parse::<i64>() {
+        Ok(game_id) => Ok(game_id),
+        Err(_) => client.find_game_id(game).await?.ok_or(CliError::NoResults),
+    }
+}
+
+pub(crate) async fn run(out: &mut Output, global: &GlobalArgs, args: &GameArgs, client: &HltbClient) -> Result<(), CliError> {
+    let template = global.template()?;
+    let game_id = resolve_game_id(client, &args.game.join(" ")).await?;
+    let details = client.game(game_id).await?;
+
+    if let Some(template) = template {
+        writeln!(out, "{}", template.render(&details))?;
+        return Ok(());
+    }
+
+    match global.format() {
+        OutputFormat::Json => {
+            let game = GameInfo::from_details(&details, client.base_url());
+            writeln!(out, "{}", serde_json::to_string(&Versioned::new(GameDocument { game })).unwrap())?;
+            return Ok(());
+        }
+        OutputFormat::Ndjson => {
+            writeln!(out, "{}", serde_json::to_string(&Versioned::new(GameInfo::from_details(&details, client.base_url()))).unwrap())?;
+            return Ok(());
+        }
+        format @ (OutputFormat::Markdown | OutputFormat::Html) => {
+            print_report(out, format, client, &[ReportSection { title: None, rows: vec![ReportRow::from_details(&details)] }], &format!("HowLongToBeat: {}", details.game_name)).await?;
+            return Ok(());
+        }
+        OutputFormat::Raw => {
+            writeln!(out, "{}", serde_json::to_string(&details).unwrap())?;
+            return Ok(());
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            print_delimited(out, global.delimited().unwrap(), &global.columns, [&details])?;
+            return Ok(());
+        }
+        OutputFormat::Text => {}
+    }
+
+    let terminal = out.terminal;
+    let image = if terminal.images && !details.game_image.is_empty() {
+        let bytes = client.fetch_image(&details.game_image).await?;
+        Some(load_image(&bytes, &details.game_name)?)
+    } else {
+        None
+    };
+    display_game_details(out, &terminal, &details, image.as_ref())
+}
@@ -0,0 +1,6 @@
+pub(crate) mod batch;
+pub(crate) mod cache;
+pub(crate) mod compare;
+pub(crate) mod config;
+pub(crate) mod game;
+pub(crate) mod search;
@@ -0,0 +1,182 @@
+use std::io::Write;
+use colored::Colorize;
+use futures::{StreamExt, TryStreamExt};
+use howlongtobeat::{Game, HltbClient, SearchPage, SearchQuery};
+use howlongtobeat::schema::{GameSummary, Versioned};
+use unicode_width::UnicodeWidthStr;
+use crate::args::{GlobalArgs, Layout, OutputFormat, SavedSearchAction, SavedSearchArgs, SearchArgs, SearchCommand, SearchFlags};
+use crate::cli_error::CliError;
+use crate::images::fetch_images;
+use crate::layout::{CardRenderer, CompactRenderer, TableRenderer};
+use crate::locale::{fill, messages};
+use crate::render::{DelimitedRenderer, JsonRenderer, RawRenderer, Output, Renderer, TemplateRenderer};
+use crate::report::{print_report, ReportRow, ReportSection};
+use crate::saved_searches::{describe, to_table, SavedSearches};
+use crate::template::Template;
+
+/// With `--min-submissions`, forgets the times too few people submitted and
+/// leaves out games without any other time, returning how many were left out.
+/// Nothing is left out at 0, not even games nobody submitted a time for.
+fn drop_unreliable(page: &mut SearchPage, flags: &SearchFlags) -> u32 {
+    let Some(min_submissions) = flags.min_submissions.filter(|min_submissions| *min_submissions > 0) else { return 0 };
+    for game in page.data.iter_mut() {
+        game.drop_unreliable_times(min_submissions);
+    }
+    let before = page.data.len();
+    page.data.retain(Game::has_times);
+    let dropped = (before - page.data.len()) as u32;
+    page.count = page.count.saturating_sub(dropped);
+    dropped
+}
+
+/// Fetches pages until `limit` games have been collected, merging them into the first page.
+async fn search_all(client: &HltbClient, query: &SearchQuery, flags: &SearchFlags) -> Result<SearchPage, CliError> {
+    let pages = client.search_pages(query);
+    futures::pin_mut!(pages);
+
+    let Some(mut merged) = pages.try_next().await? else { unreachable!("the first page is always fetched") };
+    drop_unreliable(&mut merged, flags);
+    while merged.data.len() < flags.limit {
+        match pages.try_next().await? {
+            Some(mut page) => {
+                merged.count = merged.count.saturating_sub(drop_unreliable(&mut page, flags));
+                merged.data.extend(page.data);
+            }
+            None => break,
+        }
+    }
+    merged.data.truncate(flags.limit);
+    Ok(merged)
+}
+
+/// Calls `on_game` for every result as soon as its page arrives, across pages
+/// with `--all`, and returns how many games there were.
+pub(crate) async fn stream_results(client: &HltbClient, query: &SearchQuery, flags: &SearchFlags, mut on_game: impl FnMut(&Game) -> Result<(), CliError>) -> Result<usize, CliError> {
+    let limit = if flags.all { flags.limit } else { usize::MAX };
+    let pages = client.search_pages(query).take(if flags.all { usize::MAX } else { 1 });
+    futures::pin_mut!(pages);
+
+    let mut count = 0;
+    while let Some(mut page) = pages.try_next().await? {
+        drop_unreliable(&mut page, flags);
+        for game in page.data.iter().take(limit - count) {
+            on_game(game)?;
+        }
+        count += page.data.len().min(limit - count);
+        if count >= limit { break }
+    }
+
+    if flags.fail_if_empty && count == 0 {
+        return Err(CliError::NoResults);
+    }
+    Ok(count)
+}
+
+/// Runs the search described by `query` and `flags`, honoring `--all`.
+pub(crate) async fn fetch_results(client: &HltbClient, query: &SearchQuery, flags: &SearchFlags) -> Result<SearchPage, CliError> {
+    let res = if flags.all {
+        search_all(client, query, flags).await?
+    } else {
+        let mut page = client.search(query).await?;
+        drop_unreliable(&mut page, flags);
+        page
+    };
+
+    if flags.fail_if_empty && res.data.is_empty() {
+        return Err(CliError::NoResults);
+    }
+    Ok(res)
+}
+
+pub(crate) async fn run(out: &mut Output, global: &GlobalArgs, command: &SearchCommand, client: &HltbClient) -> Result<(), CliError> {
+    match &command.saved {
+        None => search(out, global, &command.args, client).await,
+        Some(SavedSearchAction::Save(args)) => save(out, args),
+        // the saved words and filters are already filled in, like config file settings
+        Some(SavedSearchAction::Run(args)) => search(out, global, &args.search, client).await,
+        Some(SavedSearchAction::List) => list(out),
+        Some(SavedSearchAction::Delete(args)) => {
+            let mut searches = SavedSearches::load()?;
+            searches.remove(&args.name)?;
+            searches.write()?;
+            writeln!(out, "{}", fill(messages().search_deleted, &[&args.name]))?;
+            Ok(())
+        }
+    }
+}
+
+fn save(out: &mut Output, args: &SavedSearchArgs) -> Result<(), CliError> {
+    let search = to_table(&args.search);
+    let mut searches = SavedSearches::load()?;
+    searches.insert(&args.name, search);
+    searches.write()?;
+    let path = searches.path().map(|path| path.display().to_string()).unwrap_or_default();
+    writeln!(out, "{}", fill(messages().search_saved, &[&args.name, &path]))?;
+    Ok(())
+}
+
+fn list(out: &mut Output) -> Result<(), CliError> {
+    let searches = SavedSearches::load()?;
+    let width = searches.iter().map(|(name, _)| name.width()).max().unwrap_or(0);
+    for (name, search) in searches.iter() {
+        let padding = " ".repeat(width - name.width());
+        writeln!(out, "{}{}  {}", name.bold(), padding, describe(search))?;
+    }
+    if width == 0 {
+        let path = searches.path().map(|path| path.display().to_string()).unwrap_or_default();
+        writeln!(out, "{}", fill(messages().no_saved_searches, &[&path]))?;
+    }
+    Ok(())
+}
+
+async fn search(out: &mut Output, global: &GlobalArgs, args: &SearchArgs, client: &HltbClient) -> Result<(), CliError> {
+    let template = global.template()?;
+    let query = args.flags.to_query(&args.search);
+
+    if template.is_none() && global.format() == OutputFormat::Ndjson {
+        stream_results(client, &query, &args.flags, |game| {
+            let line = Versioned::new(GameSummary::from_game(game, client.base_url()));
+            writeln!(out, "{}", serde_json::to_string(&line).unwrap())?;
+            Ok(())
+        }).await?;
+        return Ok(());
+    }
+
+    let res = fetch_results(client, &query, &args.flags).await?;
+
+    match (&template, global.format()) {
+        (None, format @ (OutputFormat::Markdown | OutputFormat::Html)) => {
+            let rows = res.data.iter().map(ReportRow::from_game).collect();
+            let title = match args.search.join(" ") {
+                terms if terms.trim().is_empty() => "HowLongToBeat".to_string(),
+                terms => format!("HowLongToBeat: {}", terms),
+            };
+            print_report(out, format, client, &[ReportSection { title: None, rows }], &title).await?;
+            Ok(())
+        }
+        _ => render_results(out, global, &args.flags, client, &query, template.as_ref(), &res).await,
+    }
+}
+
+/// Writes `res` using the template, or the renderer for the output format
+/// or `--layout`. Markdown, HTML and NDJSON are written by the commands.
+pub(crate) async fn render_results(out: &mut Output, global: &GlobalArgs, flags: &SearchFlags, client: &HltbClient, query: &SearchQuery, template: Option<&Template>, res: &SearchPage) -> Result<(), CliError> {
+    let terminal = out.terminal;
+    let first = (query.page as u64 - 1).saturating_mul(query.size as u64).saturating_add(1);
+    let renderer: Box<dyn Renderer + '_> = match (template, global.format()) {
+        (Some(template), _) => Box::new(TemplateRenderer { template }),
+        (None, OutputFormat::Json) => Box::new(JsonRenderer { base_url: client.base_url() }),
+        (None, OutputFormat::Raw) => Box::new(RawRenderer),
+        (None, OutputFormat::Csv | OutputFormat::Tsv) => Box::new(DelimitedRenderer { delimited: global.delimited().unwrap(), columns: &global.columns }),
+        (None, OutputFormat::Text) => match flags.layout {
+            Layout::Cards => {
+                let images = if terminal.images { fetch_images(client, &res.data).await? } else { Vec::new() };
+                Box::new(CardRenderer { terminal, info: flags.info, pace: flags.pace, terms: query.terms.join(" "), first, images })
+            }
+            Layout::Table => Box::new(TableRenderer { terminal, first, pace: flags.pace }),
+            Layout::Compact => Box::new(CompactRenderer { pace: flags.pace }),
+        },
+        (None, OutputFormat::Ndjson | OutputFormat::Markdown | OutputFormat::Html) => unreachable!("written without a renderer"),
+    };
+    renderer.render(out, res)
+}
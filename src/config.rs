@@ -0,0 +1,404 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use clap::{Arg, ArgAction, ArgMatches, Command, CommandFactory, FromArgMatches};
+use clap::builder::{BoolishValueParser, Resettable};
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::parser::ValueSource;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use toml::{Table, Value};
+use crate::args::Cli;
+use crate::cli_error::CliError;
+use crate::saved_searches::{SavedSearches, TERMS};
+
+/// Options that only make sense on the command line.
+const COMMAND_LINE_ONLY: [&str; 4] = ["config", "profile", "help", "version"];
+
+/// `config.toml`: defaults for any long option, named like the option without
+/// its dashes, and `[profiles.<name>]` tables that add to or override them.
+#[derive(Deserialize, Default)]
+struct Config {
+    #[serde(default)]
+    profiles: BTreeMap<String, Table>,
+    #[serde(flatten)]
+    defaults: Table,
+}
+
+/// `~/.config/howlongtobeat/config.toml`, or wherever the platform keeps configuration.
+pub(crate) fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("howlongtobeat").join("config.toml"))
+}
+
+/// Where a setting came from, from weakest to strongest.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Source {
+    Default,
+    Config,
+    Profile(String),
+    SavedSearch(String),
+    Environment,
+    CommandLine,
+}
+
+/// An option with the value it ends up with.
+pub(crate) struct Setting {
+    pub(crate) name: String,
+    /// None for options without a value by default, like `proxy`.
+    pub(crate) value: Option<Value>,
+    pub(crate) source: Source,
+}
+
+/// The config file that was read, and every option of the default search command.
+pub(crate) struct Effective {
+    pub(crate) path: Option<PathBuf>,
+    pub(crate) loaded: bool,
+    pub(crate) profile: Option<String>,
+    pub(crate) settings: Vec<Setting>,
+}
+
+pub(crate) fn config_error(path: &Path, reason: impl std::fmt::Display) -> CliError {
+    CliError::Config(format!("{}: {}", path.display(), reason))
+}
+
+/// Parses the TOML in `contents`, read from `path`, reporting errors with their line.
+pub(crate) fn parse_toml<T: DeserializeOwned>(path: &Path, contents: &str) -> Result<T, CliError> {
+    toml::from_str(contents).map_err(|e| {
+        let line = e.span().map(|span| contents[..span.start].lines().count().max(1)).unwrap_or(1);
+        config_error(path, format!("line {}: {}", line, e.message().replace('\n', ", ")))
+    })
+}
+
+impl Config {
+    /// Reads `path`, or the default file if there is one.
+    fn load(path: Option<&Path>) -> Result<(Config, Option<PathBuf>, bool), CliError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok((Config::default(), None, false)),
+            },
+        };
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !required => return Ok((Config::default(), Some(path), false)),
+            Err(e) => return Err(config_error(&path, e)),
+        };
+        Ok((parse_toml(&path, &contents)?, Some(path), true))
+    }
+
+    /// The defaults with the profile's settings on top, each with where it came from.
+    fn settings(&self, profile: Option<&str>) -> Result<Vec<(String, Value, Source)>, String> {
+        let mut settings: BTreeMap<String, (Value, Source)> = self.defaults.iter()
+            .map(|(name, value)| (name.clone(), (value.clone(), Source::Config)))
+            .collect();
+        if let Some(name) = profile {
+            let Some(profile) = self.profiles.get(name) else {
+                let known = self.profiles.keys().map(String::as_str).collect::<Vec<&str>>().join(", ");
+                return Err(if known.is_empty() {
+                    format!("unknown profile {:?}, no profiles are defined", name)
+                } else {
+                    format!("unknown profile {:?}, expected one of: {}", name, known)
+                });
+            };
+            for (setting, value) in profile {
+                settings.insert(setting.clone(), (value.clone(), Source::Profile(name.to_string())));
+            }
+        }
+        Ok(settings.into_iter().map(|(name, (value, source))| (name, value, source)).collect())
+    }
+}
+
+pub(crate) fn scalar(value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Integer(number) => Ok(number.to_string()),
+        Value::Float(number) => Ok(number.to_string()),
+        Value::Boolean(flag) => Ok(flag.to_string()),
+        _ => Err("expected a string, number or boolean".to_string()),
+    }
+}
+
+/// The command line arguments that set `arg` to `value`.
+fn to_args(arg: &Arg, value: &Value) -> Result<Vec<OsString>, String> {
+    let option = format!("--{}", arg.get_long().unwrap_or_default());
+    if is_flag(arg) {
+        return match value {
+            Value::Boolean(true) => Ok(vec![option.into()]),
+            Value::Boolean(false) => Ok(Vec::new()),
+            _ => Err("expected true or false".to_string()),
+        };
+    }
+    let values = match value {
+        Value::Array(values) => values.iter().map(scalar).collect::<Result<Vec<String>, String>>()?,
+        value => vec![scalar(value)?],
+    };
+    Ok(values.into_iter().map(|value| format!("{}={}", option, value).into()).collect())
+}
+
+/// The innermost subcommand that was given, and its matches.
+fn leaf<'a>(command: &'a Command, matches: &'a ArgMatches) -> (&'a Command, &'a ArgMatches) {
+    match matches.subcommand() {
+        Some((name, sub_matches)) => match command.find_subcommand(name) {
+            Some(subcommand) => leaf(subcommand, sub_matches),
+            None => (command, matches),
+        },
+        None => (command, matches),
+    }
+}
+
+fn has_long(command: &Command, name: &str) -> bool {
+    command.get_arguments().any(|arg| arg.get_long() == Some(name))
+        || command.get_subcommands().any(|subcommand| has_long(subcommand, name))
+}
+
+/// Whether `arg` was given on the command line or in the environment.
+fn given(matches: &ArgMatches, arg: &Arg) -> bool {
+    matches!(matches.value_source(arg.get_id().as_str()), Some(ValueSource::CommandLine | ValueSource::EnvVariable))
+}
+
+/// Exits like on any other mistake on the command line when search options
+/// come before a command, where they would go unused, like `--size 1 game 1`.
+fn check_command_options(command: &Command, matches: &ArgMatches) {
+    let Some((name, _)) = matches.subcommand() else { return };
+    if let Some(arg) = command.get_arguments().find(|arg| !arg.is_global_set() && given(matches, arg)) {
+        let message = format!("the argument '{}' can't be used before the {} command", arg, name);
+        command.clone().error(ErrorKind::ArgumentConflict, message).exit();
+    }
+}
+
+/// Whether `a` and `b` can't be used together, whichever of them declares it.
+fn conflicts(command: &Command, a: &Arg, b: &Arg) -> bool {
+    command.get_arg_conflicts_with(a).iter().any(|arg| arg.get_id() == b.get_id())
+        || command.get_arg_conflicts_with(b).iter().any(|arg| arg.get_id() == a.get_id())
+}
+
+/// The command line interface, with every flag also taking `=true` or
+/// `=false`, so `--info=false` turns off `info = true` for one run.
+pub(crate) fn command() -> Command {
+    flag_values(Cli::command())
+}
+
+fn flag_values(command: Command) -> Command {
+    let names: Vec<String> = command.get_subcommands().map(|subcommand| subcommand.get_name().to_string()).collect();
+    let mut command = command.mut_args(|arg| match arg.get_action() {
+        ArgAction::SetTrue => arg.action(ArgAction::Set)
+            .value_parser(BoolishValueParser::new())
+            .value_name("BOOL")
+            .num_args(0..=1)
+            .require_equals(true)
+            .default_value("false")
+            .default_missing_value("true")
+            .hide_default_value(true)
+            .hide_possible_values(true),
+        _ => arg,
+    });
+    for name in names {
+        command = command.mut_subcommand(name, flag_values);
+    }
+    command
+}
+
+/// Whether `arg` is a flag, that may take `=true` or `=false`.
+fn is_flag(arg: &Arg) -> bool {
+    !arg.get_action().takes_values() || arg.get_num_args() == Some((0..=1).into())
+}
+
+/// `command` without any option requiring another. They are checked on the
+/// command line alone, settings from files just go unused without the option
+/// they need, like a default `limit` without `--all`.
+fn without_requirements(command: Command) -> Command {
+    let names: Vec<String> = command.get_subcommands().map(|subcommand| subcommand.get_name().to_string()).collect();
+    let mut command = command.mut_args(|arg| arg.requires(Resettable::Reset));
+    for name in names {
+        command = command.mut_subcommand(name, without_requirements);
+    }
+    command
+}
+
+/// The long name of the option a clap error is about, e.g. `size` for `--size <SIZE>`.
+fn invalid_long(e: &clap::Error) -> Option<String> {
+    let Some(ContextValue::String(arg)) = e.get(ContextKind::InvalidArg) else { return None };
+    let long = arg.strip_prefix("--")?;
+    Some(long.split([' ', '=']).next().unwrap_or_default().to_string())
+}
+
+/// The first line of a clap error, without its `error:` prefix.
+fn clap_reason(e: &clap::Error) -> String {
+    let message = e.to_string();
+    message.lines().next().unwrap_or_default().trim_start_matches("error: ").to_string()
+}
+
+/// A setting that applies to the command, as the arguments that set it.
+struct Selected<'a> {
+    name: &'a str,
+    value: &'a Value,
+    source: &'a Source,
+    args: Vec<OsString>,
+}
+
+/// The command line as given, along with the settings from the config file,
+/// profile and saved search that can fill in what it leaves out.
+pub(crate) struct Layers {
+    matches: ArgMatches,
+    /// From weakest to strongest, each name at most once.
+    settings: Vec<(String, Value, Source)>,
+    /// The words of the saved search.
+    terms: Option<String>,
+    path: Option<PathBuf>,
+    loaded: bool,
+    profile: Option<String>,
+    searches: Option<SavedSearches>,
+}
+
+impl Layers {
+    fn read(matches: ArgMatches) -> Result<Layers, CliError> {
+        let (config, path, loaded) = Config::load(matches.get_one::<PathBuf>("config").map(PathBuf::as_path))?;
+        let profile = matches.get_one::<String>("profile").cloned();
+        let mut layers = Layers { matches, settings: Vec::new(), terms: None, path, loaded, profile, searches: None };
+        layers.settings = config.settings(layers.profile.as_deref()).map_err(|reason| layers.error(&Source::Config, reason))?;
+
+        // a saved search's filters win over the same ones in the config file
+        let run = layers.matches.subcommand_matches("search").and_then(|search| search.subcommand_matches("run"));
+        if let Some(name) = run.and_then(|run| run.get_one::<String>("name")).cloned() {
+            let searches = SavedSearches::load()?;
+            for (setting, value) in searches.get(&name)? {
+                if setting == TERMS {
+                    layers.terms = Some(scalar(value).map_err(|reason| searches.error(format!("{}: {}", TERMS, reason)))?);
+                    continue;
+                }
+                layers.settings.retain(|(other, _, _)| other != setting);
+                layers.settings.push((setting.clone(), value.clone(), Source::SavedSearch(name.clone())));
+            }
+            layers.searches = Some(searches);
+        }
+
+        let command = command();
+        for (name, _, source) in &layers.settings {
+            if COMMAND_LINE_ONLY.contains(&name.as_str()) || !has_long(&command, name) {
+                return Err(layers.error(source, format!("unknown option {:?}", name)));
+            }
+        }
+        Ok(layers)
+    }
+
+    /// An error in the file `source` was read from.
+    fn error(&self, source: &Source, reason: impl std::fmt::Display) -> CliError {
+        match (source, &self.searches, &self.path) {
+            (Source::SavedSearch(_), Some(searches), _) => searches.error(reason),
+            (_, _, Some(path)) => config_error(path, reason),
+            _ => CliError::Config(reason.to_string()),
+        }
+    }
+
+    /// The settings `command` has, that aren't given on the command line or in
+    /// the environment, and don't conflict with anything that is.
+    fn select(&self, command: &Command, matches: &ArgMatches) -> Result<Vec<Selected<'_>>, CliError> {
+        let mut selected = Vec::new();
+        for (name, value, source) in &self.settings {
+            // e.g. search filters when running `game`
+            let Some(arg) = command.get_arguments().find(|arg| arg.get_long() == Some(name.as_str())) else { continue };
+            if given(matches, arg) { continue }
+            if command.get_arguments().any(|other| given(matches, other) && conflicts(command, arg, other)) { continue }
+            let args = to_args(arg, value).map_err(|reason| self.error(source, format!("{}: {}", name, reason)))?;
+            selected.push(Selected { name, value, source, args });
+        }
+        Ok(selected)
+    }
+
+    /// Parses `args`, blaming errors on the selected setting they're about.
+    fn parse(&self, args: &[OsString], selected: &[Selected]) -> Result<ArgMatches, CliError> {
+        without_requirements(command()).try_get_matches_from(args).map_err(|e| {
+            match invalid_long(&e).and_then(|long| selected.iter().find(|setting| setting.name == long)) {
+                Some(setting) => self.error(setting.source, format!("{}: {}", setting.name, clap_reason(&e))),
+                None => self.error(&Source::Config, clap_reason(&e)),
+            }
+        })
+    }
+
+    /// Every option of a plain search, with the value it gets from the command
+    /// line, the environment, the config file or its default.
+    pub(crate) fn effective(&self) -> Result<Effective, CliError> {
+        let mut command = command();
+        command.build();
+        let selected = self.select(&command, &self.matches)?;
+        let program = OsString::from(command.get_name());
+        self.parse(&[vec![program], selected.iter().flat_map(|setting| setting.args.clone()).collect()].concat(), &selected)?;
+
+        let mut settings = Vec::new();
+        for arg in command.get_arguments() {
+            let Some(name) = arg.get_long() else { continue };
+            if COMMAND_LINE_ONLY.contains(&name) { continue }
+            let setting = match selected.iter().find(|setting| setting.name == name) {
+                Some(setting) => Setting { name: name.to_string(), value: Some(setting.value.clone()), source: setting.source.clone() },
+                None => {
+                    let id = arg.get_id().as_str();
+                    let raw = self.matches.get_raw(id).map(|raw| raw.map(|value| value.to_string_lossy().into_owned()).collect());
+                    let source = match self.matches.value_source(id) {
+                        Some(ValueSource::CommandLine) => Source::CommandLine,
+                        Some(ValueSource::EnvVariable) => Source::Environment,
+                        _ => Source::Default,
+                    };
+                    Setting { name: name.to_string(), value: raw.map(|raw| to_value(arg, raw)), source }
+                }
+            };
+            settings.push(setting);
+        }
+        Ok(Effective { path: self.path.clone(), loaded: self.loaded, profile: self.profile.clone(), settings })
+    }
+}
+
+/// Parses the command line, filling in every option it leaves out from the
+/// config file, `--profile` and the saved search it runs.
+pub(crate) fn parse() -> Result<(Cli, Layers), CliError> {
+    let mut args: Vec<OsString> = std::env::args_os().collect();
+    // mistakes on the command line and --help are handled like without any settings
+    let mut command = command();
+    let matches = command.try_get_matches_from_mut(&args).unwrap_or_else(|e| e.exit());
+    check_command_options(&command, &matches);
+    let layers = Layers::read(matches)?;
+    let (leaf_command, leaf_matches) = leaf(&command, &layers.matches);
+    let selected = layers.select(leaf_command, leaf_matches)?;
+
+    // options after `--` would be taken as search terms
+    let end = args.iter().position(|arg| arg == "--").unwrap_or(args.len());
+    args.splice(end..end, selected.iter().flat_map(|setting| setting.args.clone()));
+    // the saved words, unless others are given
+    let words = leaf_command.get_arguments().find(|arg| arg.get_id() == "search");
+    if let (Some(terms), Some(words)) = (&layers.terms, words) {
+        if !given(leaf_matches, words) && !terms.trim().is_empty() {
+            if !args.iter().any(|arg| arg == "--") {
+                args.push("--".into());
+            }
+            args.push(terms.into());
+        }
+    }
+
+    let matches = layers.parse(&args, &selected)?;
+    let cli = Cli::from_arg_matches(&matches).map_err(|e| layers.error(&Source::Config, clap_reason(&e)))?;
+    Ok((cli, layers))
+}
+
+/// How a value given for `arg` is written in the config file.
+fn to_value(arg: &Arg, raw: Vec<String>) -> Value {
+    // choices like the `100` column stay strings
+    let choices = !arg.get_possible_values().is_empty();
+    let typed = |raw: String| {
+        if choices {
+            Value::String(raw)
+        } else if let Ok(number) = raw.parse::<i64>() {
+            Value::Integer(number)
+        } else if let Ok(number) = raw.parse::<f64>() {
+            Value::Float(number)
+        } else {
+            Value::String(raw)
+        }
+    };
+    if is_flag(arg) {
+        Value::Boolean(raw.first().is_some_and(|raw| raw == "true"))
+    } else if matches!(arg.get_action(), ArgAction::Append) || arg.get_value_delimiter().is_some() {
+        Value::Array(raw.into_iter().map(typed).collect())
+    } else {
+        typed(raw.into_iter().next().unwrap_or_default())
+    }
+}
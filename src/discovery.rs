@@ -0,0 +1,136 @@
+use regex::Regex;
+use crate::client::HltbClient;
+use crate::error::{Error, Result};
+use crate::query::SearchQuery;
+
+/// A way of extracting candidate search hashes from a frontend script.
+pub struct Strategy {
+    pub name: &'static str,
+    extract: fn(&str) -> Vec<String>,
+}
+
+/// Strategies in the order they are tried, the most specific first.
+pub const STRATEGIES: &[Strategy] = &[
+    Strategy { name: "concat", extract: extract_concat },
+    Strategy { name: "fetch-url", extract: extract_fetch_url },
+    Strategy { name: "key-value", extract: extract_key_value },
+];
+
+/// Where and how the search hash was found.
+#[derive(Clone, Debug)]
+pub struct Discovery {
+    pub hash: String,
+    pub strategy: &'static str,
+    pub script: String,
+}
+
+/// `"/api/search/".concat("abc")`, possibly split over several `.concat("...")` calls.
+fn extract_concat(script: &str) -> Vec<String> {
+    let re = Regex::new(r#""/api/search/"((?:\.concat\("[a-zA-Z0-9]+"\))+)"#).unwrap();
+    let part = Regex::new(r#"\.concat\("([a-zA-Z0-9]+)"\)"#).unwrap();
+    re.captures_iter(script)
+        .map(|captures| {
+            part.captures_iter(&captures[1])
+                .map(|part| part[1].to_string())
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// A complete URL literal, e.g. `fetch("/api/search/abc")` or `` `/api/search/abc` ``.
+fn extract_fetch_url(script: &str) -> Vec<String> {
+    let re = Regex::new(r#"["'`]/api/search/([a-zA-Z0-9]{8,})["'`]"#).unwrap();
+    re.captures_iter(script).map(|captures| captures[1].to_string()).collect()
+}
+
+/// A hash split into a key and a value, e.g. `{key:"abc",value:"def"}`.
+fn extract_key_value(script: &str) -> Vec<String> {
+    let re = Regex::new(r#"key:\s*"([a-zA-Z0-9]+)",\s*value:\s*"([a-zA-Z0-9]+)""#).unwrap();
+    re.captures_iter(script).map(|captures| format!("{}{}", &captures[1], &captures[2])).collect()
+}
+
+/// Returns the script paths referenced by the homepage, `_app` first since it
+/// has historically contained the search code.
+fn find_script_paths(html: &str) -> Vec<String> {
+    let re = Regex::new(r#"/_next/static/chunks/[a-zA-Z0-9_/\[\]\-.]+?\.js"#).unwrap();
+    let mut paths: Vec<String> = Vec::new();
+    for found in re.find_iter(html) {
+        if !paths.iter().any(|path| path == found.as_str()) {
+            paths.push(found.as_str().to_string());
+        }
+    }
+    paths.sort_by_key(|path| !path.contains("/pages/_app-"));
+    paths
+}
+
+pub(crate) async fn discover(client: &HltbClient) -> Result<Discovery> {
+    let html = client.get_text(client.base_url()).await?;
+
+    let script_paths = find_script_paths(&html);
+    if script_paths.is_empty() {
+        return Err(Error::HashDiscovery("no scripts found on the homepage".to_string()));
+    }
+
+    let probe = SearchQuery { size: 1, ..Default::default() };
+    let probe_body = serde_json::to_string(&probe.to_body())?;
+
+    let mut rejected = Vec::new();
+    for script_path in &script_paths {
+        let script = match client.get_text(&format!("{}{}", client.base_url(), script_path)).await {
+            Ok(script) => script,
+            // chunks can disappear between deployments, the others may still have the hash
+            Err(Error::Status { .. }) => continue,
+            Err(e) => return Err(e),
+        };
+
+        for strategy in STRATEGIES {
+            for hash in (strategy.extract)(&script) {
+                if rejected.contains(&hash) { continue }
+                if client.post_search(&hash, &probe_body).await.is_ok() {
+                    return Ok(Discovery { hash, strategy: strategy.name, script: script_path.clone() });
+                }
+                rejected.push(hash);
+            }
+        }
+    }
+
+    Err(Error::HashDiscovery(format!(
+        "no strategy found a working hash in {} scripts ({} candidates rejected)",
+        script_paths.len(),
+        rejected.len()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concat_joins_every_part() {
+        let script = r#"fetch("/api/search/".concat("5f2c9bd1").concat("a0e34c77"),e)"#;
+        assert_eq!(extract_concat(script), ["5f2c9bd1a0e34c77"]);
+    }
+
+    #[test]
+    fn fetch_url_finds_complete_urls_in_any_quotes() {
+        let script = r#"fetch("/api/search/5f2c9bd1a0e34c77",e);g('/api/search/0123456789abcdef');h(`/api/search/fedcba98`)"#;
+        assert_eq!(extract_fetch_url(script), ["5f2c9bd1a0e34c77", "0123456789abcdef", "fedcba98"]);
+    }
+
+    #[test]
+    fn fetch_url_skips_short_and_unfinished_urls() {
+        assert!(extract_fetch_url(r#"fetch("/api/search/abc")"#).is_empty());
+        assert!(extract_fetch_url(r#"fetch("/api/search/".concat("5f2c9bd1a0e34c77"))"#).is_empty());
+    }
+
+    #[test]
+    fn key_value_joins_key_and_value() {
+        let script = r#"let t={key:"5f2c9bd1", value: "a0e34c77"},u={key:"01234567",value:"89abcdef"}"#;
+        assert_eq!(extract_key_value(script), ["5f2c9bd1a0e34c77", "0123456789abcdef"]);
+    }
+
+    #[test]
+    fn key_value_needs_both_halves() {
+        assert!(extract_key_value(r#"{key:"5f2c9bd1"}"#).is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+use std::io::Write;
+use colored::{ColoredString, Colorize};
+use howlongtobeat::{GameDetails, TimeCategory};
+use image::DynamicImage;
+use crate::cli_error::CliError;
+use crate::display_time_components::format_with_color;
+use crate::duration_formatter::DurationFormatter;
+use crate::images::print_beside_image;
+use crate::locale::messages;
+use crate::render::Terminal;
+
+fn label(text: &str) -> ColoredString {
+    text.truecolor(200, 200, 200)
+}
+
+fn category_name(category: TimeCategory) -> &'static str {
+    let m = messages();
+    match category {
+        TimeCategory::MainStory => m.main_story,
+        TimeCategory::MainExtras => m.main_extras,
+        TimeCategory::Completionist => m.completionist,
+        TimeCategory::AllPlayStyles => m.all_styles,
+        TimeCategory::CoOp => m.co_op,
+        TimeCategory::Competitive => m.competitive,
+    }
+}
+
+/// A game's name, followed by a link to its Steam store page when it has one
+/// and the terminal shows links.
+pub(crate) fn name_with_steam_link(name: ColoredString, profile_steam: u32, terminal: &Terminal) -> String {
+    if terminal.hyperlinks && profile_steam != 0 {
+        let url = format!("https://store.steampowered.com/app/{}", profile_steam);
+        format!("{} {}", name, link!(url, "[Steam Store Page]".blue().underline()))
+    } else {
+        name.to_string()
+    }
+}
+
+/// The share of players who gave up on a game, like `3.5%`, 0% when nobody played it.
+pub(crate) fn retired_share(count_retired: u32, count_comp: u32, count_backlog: u32) -> String {
+    let all_players = count_comp + count_backlog + count_retired;
+    let retired = if all_players == 0 { 0.0 } else { count_retired as f64 / all_players as f64 * 100.0 };
+    format!("{}%", messages().decimal(retired, 1))
+}
+
+fn header_lines(details: &GameDetails, terminal: &Terminal) -> Vec<String> {
+    let m = messages();
+    let mut lines = Vec::new();
+
+    lines.push(name_with_steam_link(details.game_name.bold(), details.profile_steam, terminal));
+
+    let fields = [
+        (m.developer, details.profile_dev.clone()),
+        (m.publisher, details.profile_pub.clone()),
+        (m.genres, details.profile_genre.clone()),
+        (m.platforms, details.profile_platform.clone()),
+        (m.released, details.release_dates().into_iter()
+            .map(|(region, date)| format!("{} {}", region, date))
+            .collect::<Vec<String>>()
+            .join(", ")),
+    ];
+    for (name, value) in fields {
+        if !value.is_empty() {
+            lines.push(format!("{} {}", label(&format!("{}:", name)), value));
+        }
+    }
+
+    let stats = [
+        (m.rating, if details.review_score == 0 { m.not_rated.to_string() } else { format!("{}%", details.review_score) }),
+        (m.playing, m.number(details.count_playing)),
+        (m.backlogs, m.number(details.count_backlog)),
+        (m.retired, retired_share(details.count_retired, details.count_comp, details.count_backlog)),
+        (m.beat, m.number(details.count_comp)),
+    ]
+        .into_iter()
+        .map(|(name, value)| format!("{} {}", label(&format!("{}:", name)), value))
+        .collect::<Vec<String>>()
+        .join(", ");
+    lines.push(stats);
+
+    lines
+}
+
+fn time_table_lines(details: &GameDetails) -> Vec<String> {
+    let m = messages();
+    let rows: Vec<(&str, String, u32, [String; 4])> = details.time_table().into_iter()
+        .filter(|stats| stats.count > 0)
+        .map(|stats| (
+            category_name(stats.category),
+            m.number(stats.count),
+            stats.count,
+            [stats.average.format(), stats.median.format(), stats.rushed.format(), stats.leisure.format()],
+        ))
+        .collect();
+    if rows.is_empty() {
+        return vec![m.no_times.truecolor(120, 120, 120).to_string()];
+    }
+
+    let headers = [m.polled, m.average, m.median, m.rushed, m.leisure];
+    let name_width = rows.iter().map(|(name, _, _, _)| name.chars().count()).max().unwrap_or(0);
+    let mut widths = headers.map(|header| header.chars().count());
+    for (_, polled, _, times) in &rows {
+        widths[0] = widths[0].max(polled.chars().count());
+        for (index, time) in times.iter().enumerate() {
+            widths[index + 1] = widths[index + 1].max(time.chars().count());
+        }
+    }
+
+    let mut lines = vec![format!(
+        "{:name_width$}  {}",
+        "",
+        headers.iter().zip(widths).map(|(header, width)| format!("{:>width$}", header)).collect::<Vec<String>>().join("  "),
+    ).truecolor(200, 200, 200).to_string()];
+
+    for (name, polled, count, times) in rows {
+        let mut columns = vec![format!("{:>width$}", polled, width = widths[0]).truecolor(120, 120, 120).to_string()];
+        for (index, time) in times.iter().enumerate() {
+            columns.push(format_with_color(&format!("{:>width$}", time, width = widths[index + 1]), count));
+        }
+        lines.push(format!("{}  {}", label(&format!("{:name_width$}", name)), columns.join("  ")));
+    }
+    lines
+}
+
+pub(crate) fn display_game_details(out: &mut dyn Write, terminal: &Terminal, details: &GameDetails, image: Option<&DynamicImage>) -> Result<(), CliError> {
+    let header = header_lines(details, terminal);
+    match image {
+        Some(image) => print_beside_image(out, image, &header)?,
+        None => for line in header {
+            writeln!(out, "{}", line)?;
+        },
+    }
+
+    if !details.profile_summary.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "{}", details.profile_summary.trim())?;
+    }
+
+    writeln!(out)?;
+    for line in time_table_lines(details) {
+        writeln!(out, "{}", line)?;
+    }
+    for warning in details.warnings() {
+        writeln!(out, "{}", format!("⚠ {}", messages().warning(warning)).yellow())?;
+    }
+    Ok(())
+}
@@ -1,9 +1,11 @@
 use colored::Colorize;
-use crate::Args;
 use crate::duration_formatter::DurationFormatter;
-use crate::post_result::Game;
+use crate::locale::messages;
+use crate::pace::Pace;
+use howlongtobeat::Game;
+use std::time::Duration;
 
-fn get_accuracy_color(count: u32) -> (u8, u8, u8) {
+pub(crate) fn get_accuracy_color(count: u32) -> (u8, u8, u8) {
     match count {
         0..=4 => (255, 58, 58),
         5..=9 => (204, 59, 81),
@@ -15,48 +17,71 @@ fn get_accuracy_color(count: u32) -> (u8, u8, u8) {
     }
 }
 
-fn format_with_color(value: &str, count: u32) -> String {
+pub(crate) fn format_with_color(value: &str, count: u32) -> String {
     let color = get_accuracy_color(count);
     value.to_string().truecolor(color.0, color.1, color.2).to_string()
 }
 
-pub fn display_time_components(indentation: u8, game: Game, args: &Args) -> u32 {
-    let mut lines_printed = 0u32;
+/// The times shown for a game, as (label, time, number of submissions): solo,
+/// co-op and versus for multiplayer games, otherwise main story, main + extras
+/// and completionist. Times nobody has submitted are skipped.
+pub(crate) fn time_components(game: &Game) -> Vec<(&'static str, Duration, u32)> {
+    let mut components = time_slots(game);
+    components.retain(|(_, _, count)| *count > 0);
+    components
+}
 
-    let mut components: Vec<(&str, String, u32)>;
+/// Like [`time_components`], keeping the times nobody has submitted.
+pub(crate) fn time_slots(game: &Game) -> Vec<(&'static str, Duration, u32)> {
+    let m = messages();
     if game.comp_lvl_combine == 1 {
-        components = vec![
-            ("Solo: ", game.comp_all.format(), game.comp_all_count),
-            ("Co-op:", game.invested_co.format(), game.invested_co_count),
-            ("Vs.  :", game.invested_mp.format(), game.invested_mp_count)
+        vec![
+            (m.solo, game.comp_all, game.comp_all_count),
+            (m.coop, game.invested_co, game.invested_co_count),
+            (m.versus, game.invested_mp, game.invested_mp_count)
         ]
     } else {
-        components = vec![
-            ("Main Story:   ", game.comp_main.format(), game.comp_main_count),
-            ("Main + Extra: ", game.comp_plus.format(), game.comp_plus_count),
-            ("Completionist:", game.comp_100.format(), game.comp_100_count)
-        ];
+        vec![
+            (m.main_story, game.comp_main, game.comp_main_count),
+            (m.main_extra, game.comp_plus, game.comp_plus_count),
+            (m.completionist, game.comp_100, game.comp_100_count)
+        ]
     }
-    components = components.into_iter().filter(|(_, _, count)| *count > 0).collect();
-
-    let max_length = components.iter().map(|(_, time, _)| time.len())
-        .max().unwrap_or(0);
-
-    for (name, time, count) in components {
-        let current_length = time.len();
-        let required_spaces = max_length - current_length;
-
-        if indentation > 0 { print!("\x1B[{}C", indentation) }
+}
 
-        let mut time = format_with_color(time.as_str(), count);
-        if args.info {
-            let polled = format!("({})", count).truecolor(120, 120, 120);
-            time = format!("{}{} {}", time, " ".repeat(required_spaces), polled);
-        }
-        println!("{} {}", name.truecolor(200, 200, 200), time);
+/// A line per time: its label, the time colored by accuracy and, with `info`,
+/// how many people submitted it, and with a `pace`, how long it takes to play.
+pub(crate) fn time_component_lines(game: &Game, info: bool, pace: Option<Pace>) -> Vec<String> {
+    let m = messages();
+    let components: Vec<(String, Duration, String, u32)> = time_components(game).into_iter()
+        .map(|(label, time, count)| {
+            let label = if label == m.versus { m.versus_card } else { label };
+            (format!("{}:", label), time, time.format(), count)
+        })
+        .collect();
 
-        lines_printed += 1;
-    }
+    let max_label = components.iter().map(|(label, _, _, _)| label.chars().count())
+        .max().unwrap_or(0);
+    let max_length = components.iter().map(|(_, _, time, _)| time.chars().count())
+        .max().unwrap_or(0);
+    let max_polled = components.iter().map(|(_, _, _, count)| m.number(*count).chars().count() + 2)
+        .max().unwrap_or(0);
 
-    lines_printed
+    components.into_iter()
+        .map(|(label, duration, time, count)| {
+            let label = format!("{:max_label$}", label);
+            let mut line = format!("{} {}", label.truecolor(200, 200, 200), format_with_color(time.as_str(), count));
+            let mut required_spaces = max_length - time.chars().count();
+            if info {
+                let polled = format!("({})", m.number(count));
+                line = format!("{}{} {}", line, " ".repeat(required_spaces), polled.truecolor(120, 120, 120));
+                required_spaces = max_polled - polled.chars().count();
+            }
+            if let Some(pace) = pace {
+                let estimate = pace.estimate(duration).to_string();
+                line = format!("{}{}  {}", line, " ".repeat(required_spaces), estimate.truecolor(120, 120, 120));
+            }
+            line
+        })
+        .collect()
 }
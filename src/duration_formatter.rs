@@ -1,19 +1,169 @@
+use std::sync::OnceLock;
 use std::time::Duration;
+use clap::ValueEnum;
+use crate::locale::{fill, messages};
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum TimeFormat {
+    /// Rounded to the nearest half, like 51.5h or 30m
+    #[default]
+    Short,
+    /// Hours with --time-precision decimals, like 51.5h
+    Decimal,
+    /// Hours and minutes, like 51h 30m
+    #[value(name = "hm")]
+    HoursMinutes,
+    /// Hours and minutes, like 51:30
+    Clock,
+    /// Like HowLongToBeat writes them, e.g. 51½ Hours
+    Hltb,
+    /// Total minutes, like 3090m
+    Minutes,
+    /// ISO 8601 duration, like PT51H30M
+    Iso,
+}
+
+struct Style {
+    /// `None` when `--time-format` wasn't given, so outputs can pick their own default.
+    format: Option<TimeFormat>,
+    precision: usize,
+}
+
+static STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Sets how every duration is written, once at startup.
+pub(crate) fn set_time_format(format: Option<TimeFormat>, precision: usize) {
+    let _ = STYLE.set(Style { format, precision });
+}
+
+/// The `--time-format` given on the command line, if any.
+pub(crate) fn chosen_time_format() -> Option<TimeFormat> {
+    STYLE.get().and_then(|style| style.format)
+}
 
 fn round_to_half(num: f32) -> f32 {
     (num * 2.0).round() / 2.0
 }
 
-pub trait DurationFormatter {
+pub(crate) trait DurationFormatter {
+    /// Formats the duration in the `--time-format` style, with the language's decimal separator.
     fn format(&self) -> String;
+    fn format_as(&self, format: TimeFormat, precision: usize) -> String;
 }
 
 impl DurationFormatter for Duration {
     fn format(&self) -> String {
-        match self.as_secs() {
-            seconds if seconds < 60 => format!("{}s", seconds),
-            seconds if seconds < 3600 => format!("{}m", round_to_half(seconds as f32 / 60.0)),
-            seconds => format!("{}h", round_to_half(seconds as f32 / 3600.0)),
+        let formatted = match STYLE.get() {
+            Some(style) => self.format_as(style.format.unwrap_or_default(), style.precision),
+            None => self.format_as(TimeFormat::Short, 0),
+        };
+        formatted.replace('.', messages().decimal_separator)
+    }
+
+    fn format_as(&self, format: TimeFormat, precision: usize) -> String {
+        let seconds = self.as_secs();
+        let minutes = seconds.saturating_add(30) / 60;
+        match format {
+            TimeFormat::Short => match seconds {
+                seconds if seconds < 60 => format!("{}s", seconds),
+                seconds if seconds < 3600 => format!("{}m", round_to_half(seconds as f32 / 60.0)),
+                seconds => format!("{}h", round_to_half(seconds as f32 / 3600.0)),
+            },
+            TimeFormat::Decimal => format!("{:.precision$}h", self.as_secs_f64() / 3600.0),
+            TimeFormat::HoursMinutes if minutes < 60 => format!("{}m", minutes),
+            TimeFormat::HoursMinutes if minutes.is_multiple_of(60) => format!("{}h", minutes / 60),
+            TimeFormat::HoursMinutes => format!("{}h {}m", minutes / 60, minutes % 60),
+            TimeFormat::Clock => format!("{:02}:{:02}", minutes / 60, minutes % 60),
+            TimeFormat::Hltb if seconds < 3600 => fill(messages().plural_form(&messages().hltb_mins, minutes), &[&minutes]),
+            TimeFormat::Hltb => {
+                let halves = (seconds as f64 / 1800.0).round() as u64;
+                let hours = halves / 2;
+                let half = if halves.is_multiple_of(2) { "" } else { "½" };
+                // A half hour more reads like a few, e.g. 1½ Hours and 1½ godziny
+                let count = if half.is_empty() { hours } else { 2 };
+                fill(messages().plural_form(&messages().hltb_hours, count), &[&format!("{}{}", hours, half)])
+            }
+            TimeFormat::Minutes => format!("{}m", minutes),
+            TimeFormat::Iso => {
+                let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+                let mut iso = "PT".to_string();
+                if hours > 0 { iso.push_str(&format!("{}H", hours)) }
+                if minutes > 0 { iso.push_str(&format!("{}M", minutes)) }
+                if seconds > 0 || iso == "PT" { iso.push_str(&format!("{}S", seconds)) }
+                iso
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3600;
+
+    fn format(seconds: u64, format: TimeFormat) -> String {
+        Duration::from_secs(seconds).format_as(format, 0)
+    }
+
+    #[test]
+    fn short_rounds_to_the_nearest_half() {
+        assert_eq!(format(0, TimeFormat::Short), "0s");
+        assert_eq!(format(59, TimeFormat::Short), "59s");
+        assert_eq!(format(90, TimeFormat::Short), "1.5m");
+        assert_eq!(format(HOUR, TimeFormat::Short), "1h");
+        assert_eq!(format(51 * HOUR + 1700, TimeFormat::Short), "51.5h");
+        assert_eq!(format(10_000 * HOUR, TimeFormat::Short), "10000h");
+    }
+
+    #[test]
+    fn decimal_uses_the_precision() {
+        assert_eq!(format(0, TimeFormat::Decimal), "0h");
+        assert_eq!(Duration::from_secs(51 * HOUR + 1800).format_as(TimeFormat::Decimal, 2), "51.50h");
+        assert_eq!(Duration::from_secs(HOUR / 3).format_as(TimeFormat::Decimal, 1), "0.3h");
+    }
+
+    #[test]
+    fn hours_and_minutes_leave_out_empty_parts() {
+        assert_eq!(format(0, TimeFormat::HoursMinutes), "0m");
+        assert_eq!(format(29, TimeFormat::HoursMinutes), "0m");
+        assert_eq!(format(30, TimeFormat::HoursMinutes), "1m");
+        assert_eq!(format(HOUR, TimeFormat::HoursMinutes), "1h");
+        assert_eq!(format(51 * HOUR + 1800, TimeFormat::HoursMinutes), "51h 30m");
+        assert_eq!(format(HOUR - 20, TimeFormat::HoursMinutes), "1h");
+    }
+
+    #[test]
+    fn clock_pads_to_two_digits() {
+        assert_eq!(format(0, TimeFormat::Clock), "00:00");
+        assert_eq!(format(5 * 60, TimeFormat::Clock), "00:05");
+        assert_eq!(format(51 * HOUR + 1800, TimeFormat::Clock), "51:30");
+        assert_eq!(format(1234 * HOUR, TimeFormat::Clock), "1234:00");
+    }
+
+    #[test]
+    fn hltb_writes_halves_and_units() {
+        assert_eq!(format(0, TimeFormat::Hltb), "0 Mins");
+        assert_eq!(format(1800, TimeFormat::Hltb), "30 Mins");
+        assert_eq!(format(HOUR, TimeFormat::Hltb), "1 Hour");
+        assert_eq!(format(HOUR + 1800, TimeFormat::Hltb), "1½ Hours");
+        assert_eq!(format(2 * HOUR, TimeFormat::Hltb), "2 Hours");
+        assert_eq!(format(51 * HOUR + 1700, TimeFormat::Hltb), "51½ Hours");
+    }
+
+    #[test]
+    fn minutes_and_iso_handle_zero_and_huge_durations() {
+        assert_eq!(format(0, TimeFormat::Minutes), "0m");
+        assert_eq!(format(51 * HOUR + 1800, TimeFormat::Minutes), "3090m");
+        assert_eq!(format(u64::MAX, TimeFormat::Minutes), "307445734561825860m");
+        assert_eq!(format(0, TimeFormat::Iso), "PT0S");
+        assert_eq!(format(51 * HOUR + 1800, TimeFormat::Iso), "PT51H30M");
+        assert_eq!(format(HOUR + 61, TimeFormat::Iso), "PT1H1M1S");
+        assert_eq!(format(u64::MAX, TimeFormat::Iso), "PT5124095576030431H15S");
+    }
+
+    #[test]
+    fn format_defaults_to_short() {
+        assert_eq!(Duration::from_secs(51 * HOUR + 1800).format(), "51.5h");
+    }
+}
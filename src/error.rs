@@ -0,0 +1,59 @@
+use std::fmt;
+use reqwest::StatusCode;
+
+/// Errors returned by [`HltbClient`](crate::HltbClient).
+#[derive(Debug)]
+pub enum Error {
+    /// The client settings are invalid (bad proxy URL, header, CA bundle...).
+    Config(String),
+    /// The request could not be sent or the response could not be read.
+    Network(reqwest::Error),
+    /// The search endpoint could not be found in the HowLongToBeat frontend.
+    HashDiscovery(String),
+    /// HowLongToBeat answered with a non-success status code.
+    Status { url: String, status: StatusCode },
+    /// The response did not match the expected schema.
+    Decode(serde_json::Error),
+    /// A page did not have the expected structure.
+    Parse(String),
+    /// The client is offline and the response is not in the cache.
+    NotCached(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Config(reason) => write!(f, "invalid configuration: {}", reason),
+            Error::Network(e) => write!(f, "network error: {}", e),
+            Error::HashDiscovery(reason) => write!(f, "could not discover the search endpoint: {}", reason),
+            Error::Status { url, status } => write!(f, "{} responded with {}", url, status),
+            Error::Decode(e) => write!(f, "unexpected response from HowLongToBeat: {}", e),
+            Error::Parse(reason) => write!(f, "unexpected response from HowLongToBeat: {}", reason),
+            Error::NotCached(what) => write!(f, "{} is not cached, run without --offline to fetch it", what),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Network(e) => Some(e),
+            Error::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Network(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Decode(e)
+    }
+}
@@ -0,0 +1,191 @@
+use std::time::Duration;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use crate::error::{Error, Result};
+use crate::post_result::deserialize_duration;
+
+/// Everything the HowLongToBeat game page (`/game/{id}`) knows about a game.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameDetails {
+    pub game_id: i64,
+    pub game_name: String,
+    #[serde(default)]
+    pub game_alias: String,
+    #[serde(default)]
+    pub game_type: String,
+    #[serde(default)]
+    pub game_image: String,
+    #[serde(default)]
+    pub profile_summary: String,
+    #[serde(default)]
+    pub profile_genre: String,
+    #[serde(default)]
+    pub profile_dev: String,
+    #[serde(default)]
+    pub profile_pub: String,
+    #[serde(default)]
+    pub profile_platform: String,
+    #[serde(default)]
+    pub profile_steam: u32,
+    #[serde(default)]
+    pub release_world: String,
+    #[serde(default)]
+    pub release_na: String,
+    #[serde(default)]
+    pub release_eu: String,
+    #[serde(default)]
+    pub release_jp: String,
+    #[serde(default)]
+    pub review_score: u8,
+    #[serde(default)]
+    pub count_comp: u32,
+    #[serde(default)]
+    pub count_playing: u32,
+    #[serde(default)]
+    pub count_backlog: u32,
+    #[serde(default)]
+    pub count_retired: u32,
+    #[serde(default)]
+    pub comp_lvl_combine: i64,
+
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_main: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_plus: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_100: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_all: Duration,
+
+    #[serde(default)]
+    pub comp_main_count: u32,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_main_avg: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_main_med: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_main_l: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_main_h: Duration,
+
+    #[serde(default)]
+    pub comp_plus_count: u32,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_plus_avg: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_plus_med: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_plus_l: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_plus_h: Duration,
+
+    #[serde(default)]
+    pub comp_100_count: u32,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_100_avg: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_100_med: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_100_l: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_100_h: Duration,
+
+    #[serde(default)]
+    pub comp_all_count: u32,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_all_avg: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_all_med: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_all_l: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub comp_all_h: Duration,
+
+    #[serde(default)]
+    pub invested_co_count: u32,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub invested_co_avg: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub invested_co_med: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub invested_co_l: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub invested_co_h: Duration,
+
+    #[serde(default)]
+    pub invested_mp_count: u32,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub invested_mp_avg: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub invested_mp_med: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub invested_mp_l: Duration,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub invested_mp_h: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum TimeCategory {
+    #[strum(serialize = "Main Story")]
+    MainStory,
+    #[strum(serialize = "Main + Extras")]
+    MainExtras,
+    #[strum(serialize = "Completionist")]
+    Completionist,
+    #[strum(serialize = "All PlayStyles")]
+    AllPlayStyles,
+    #[strum(serialize = "Co-Op")]
+    CoOp,
+    #[strum(serialize = "Competitive")]
+    Competitive,
+}
+
+/// One row of the time table shown on the game page.
+#[derive(Clone, Debug)]
+pub struct TimeStats {
+    pub category: TimeCategory,
+    pub count: u32,
+    pub average: Duration,
+    pub median: Duration,
+    pub rushed: Duration,
+    pub leisure: Duration,
+}
+
+impl GameDetails {
+    /// Release dates by region (`NA`, `EU`, `JP`, `World`), skipping unknown ones.
+    pub fn release_dates(&self) -> Vec<(&'static str, &str)> {
+        [("NA", &self.release_na), ("EU", &self.release_eu), ("JP", &self.release_jp), ("World", &self.release_world)]
+            .into_iter()
+            .filter(|(_, date)| !date.is_empty())
+            .map(|(region, date)| (region, date.as_str()))
+            .collect()
+    }
+
+    /// The complete time table, including categories without any submissions.
+    pub fn time_table(&self) -> Vec<TimeStats> {
+        let row = |category, count, average, median, rushed, leisure| TimeStats { category, count, average, median, rushed, leisure };
+        vec![
+            row(TimeCategory::MainStory, self.comp_main_count, self.comp_main_avg, self.comp_main_med, self.comp_main_l, self.comp_main_h),
+            row(TimeCategory::MainExtras, self.comp_plus_count, self.comp_plus_avg, self.comp_plus_med, self.comp_plus_l, self.comp_plus_h),
+            row(TimeCategory::Completionist, self.comp_100_count, self.comp_100_avg, self.comp_100_med, self.comp_100_l, self.comp_100_h),
+            row(TimeCategory::AllPlayStyles, self.comp_all_count, self.comp_all_avg, self.comp_all_med, self.comp_all_l, self.comp_all_h),
+            row(TimeCategory::CoOp, self.invested_co_count, self.invested_co_avg, self.invested_co_med, self.invested_co_l, self.invested_co_h),
+            row(TimeCategory::Competitive, self.invested_mp_count, self.invested_mp_avg, self.invested_mp_med, self.invested_mp_l, self.invested_mp_h),
+        ]
+    }
+}
+
+/// Extracts the game from the Next.js data embedded in a game page.
+pub(crate) fn parse_game_page(html: &str) -> Result<GameDetails> {
+    let re = Regex::new(r#"(?s)<script id="__NEXT_DATA__" type="application/json"[^>]*>(.*?)</script>"#).unwrap();
+    let next_data = re.captures(html)
+        .and_then(|captures| captures.get(1))
+        .ok_or_else(|| Error::Parse("no __NEXT_DATA__ script on the game page".to_string()))?
+        .as_str();
+
+    let next_data: serde_json::Value = serde_json::from_str(next_data)?;
+    let game = next_data.pointer("/props/pageProps/game/data/game/0")
+        .ok_or_else(|| Error::Parse("no game in the page data".to_string()))?;
+    Ok(GameDetails::deserialize(game)?)
+}
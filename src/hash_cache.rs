@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::cache_file::{now, write_atomically, DEFAULT_TTL};
+
+/// The cached hash, along with the origin it belongs to and when it was discovered (Unix seconds).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CachedHash {
+    pub base_url: String,
+    pub hash: String,
+    pub discovered_at: u64,
+}
+
+/// On-disk cache of the discovered search hash, so it doesn't have to be
+/// looked up on every run.
+#[derive(Clone, Debug)]
+pub struct HashCache {
+    path: Option<PathBuf>,
+    ttl: Duration,
+}
+
+impl HashCache {
+    pub fn new(path: impl Into<PathBuf>, ttl: Duration) -> Self {
+        HashCache { path: Some(path.into()), ttl }
+    }
+
+    /// A cache that never stores anything, forcing discovery on every search.
+    pub fn disabled() -> Self {
+        HashCache { path: None, ttl: Duration::ZERO }
+    }
+
+    /// `$XDG_CACHE_HOME/howlongtobeat/search-hash.json` (or the platform equivalent).
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("howlongtobeat").join("search-hash.json"))
+    }
+
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        HashCache { ttl, ..self }
+    }
+
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the cache contents, regardless of their age.
+    pub fn entry(&self) -> Option<CachedHash> {
+        let contents = fs::read_to_string(self.path.as_ref()?).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Returns the hash cached for `base_url` if it exists and is younger than the TTL.
+    pub fn load(&self, base_url: &str) -> Option<String> {
+        let entry = self.entry()?;
+        if entry.base_url != base_url { return None }
+        let age = now().saturating_sub(entry.discovered_at);
+        if age < self.ttl.as_secs() { Some(entry.hash) } else { None }
+    }
+
+    /// Stores the hash. Failures are ignored, the cache is only an optimization.
+    pub fn store(&self, base_url: &str, hash: &str) {
+        let Some(path) = &self.path else { return };
+        let entry = CachedHash { base_url: base_url.to_string(), hash: hash.to_string(), discovered_at: now() };
+        let _ = write_atomically(path, &serde_json::to_string(&entry).unwrap());
+    }
+
+    pub fn clear(&self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+impl Default for HashCache {
+    fn default() -> Self {
+        match Self::default_path() {
+            Some(path) => HashCache::new(path, DEFAULT_TTL),
+            None => HashCache::disabled(),
+        }
+    }
+}
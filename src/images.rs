@@ -0,0 +1,64 @@
+use std::io::Write;
+use futures::future::try_join_all;
+use howlongtobeat::{Game, HltbClient};
+use image::DynamicImage;
+use viuer::Config;
+use crate::cli_error::CliError;
+
+pub(crate) fn get_terminal_image_dimensions(image: &DynamicImage) -> (u32, u32) {
+    let ratio = image.width() as f32 / image.height() as f32;
+    let width: u32;
+    let height: u32;
+    if ratio > 1.0 {
+        height = 7u32;
+        width = (height as f32 * ratio) as u32;
+    } else {
+        width = 10u32;
+        height = (width as f32 / ratio) as u32;
+    }
+    (width, (height as f32 / 2.15).round() as u32)
+}
+
+pub(crate) fn load_image(bytes: &[u8], name: &str) -> Result<DynamicImage, CliError> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| CliError::Image(format!("{}: {}", name, e)))?;
+    Ok(DynamicImage::ImageRgba8(image.to_rgba8()))
+}
+
+pub(crate) async fn fetch_images(client: &HltbClient, games: &[Game]) -> Result<Vec<DynamicImage>, CliError> {
+    let futures = games.iter().map(|game| {
+        async move {
+            let image = client.fetch_cover(game).await?;
+            load_image(&image, &game.game_name)
+        }
+    });
+    try_join_all(futures).await
+}
+
+/// Prints `lines` to the right of `image`, leaving the cursor below both.
+/// `viuer` draws straight to stdout, so `out` must be stdout as well.
+pub(crate) fn print_beside_image(out: &mut dyn Write, image: &DynamicImage, lines: &[String]) -> Result<(), CliError> {
+    let (width, height) = get_terminal_image_dimensions(image);
+    let conf = Config {
+        absolute_offset: false,
+        width: Some(width),
+        height: Some(height),
+        ..Default::default()
+    };
+    out.flush()?;
+    viuer::print(image, &conf).map_err(|e| CliError::Image(e.to_string()))?;
+
+    // move cursor to the top of the image
+    write!(out, "\x1B[{}A", height)?;
+    for line in lines {
+        // move cursor to the right of the image
+        writeln!(out, "\x1B[{}C{}", width + 1, line)?;
+    }
+
+    let lines_printed = lines.len() as u32;
+    if height > lines_printed {
+        write!(out, "{}", "\n".repeat((height - lines_printed) as usize))?;
+    }
+    Ok(())
+}
+
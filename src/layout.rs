@@ -0,0 +1,288 @@
+use std::io::{self, Write};
+use std::time::Duration;
+use colored::Colorize;
+use howlongtobeat::{Game, SearchPage};
+use image::DynamicImage;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use crate::cli_error::CliError;
+use crate::display_game_details::{name_with_steam_link, retired_share};
+use crate::display_time_components::{format_with_color, time_component_lines, time_slots};
+use crate::duration_formatter::DurationFormatter;
+use crate::images::print_beside_image;
+use crate::locale::{fill, messages};
+use crate::pace::Pace;
+use crate::render::{Renderer, Terminal};
+
+const SEPARATOR: &str = "  ";
+/// Names are never cut shorter than this, even if the row then overflows the terminal.
+const MIN_NAME_WIDTH: usize = 12;
+
+/// Shortens `text` to at most `width` terminal columns, ending it with … when cut.
+pub(crate) fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width { return text.to_string() }
+
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width + 1 > width { break }
+        truncated.push(c);
+        used += char_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Pads `text` with spaces to `width` terminal columns.
+fn pad(text: &str, width: usize, right_aligned: bool) -> String {
+    let padding = " ".repeat(width.saturating_sub(text.width()));
+    if right_aligned { format!("{}{}", padding, text) } else { format!("{}{}", text, padding) }
+}
+
+enum Style {
+    Name,
+    /// A time, colored by how many people submitted it.
+    Time(u32),
+    Warning,
+    Plain,
+}
+
+struct TableCell {
+    text: String,
+    style: Style,
+}
+
+impl TableCell {
+    fn time(time: Duration, count: u32) -> Self {
+        if count == 0 || time.is_zero() {
+            TableCell { text: "–".to_string(), style: Style::Plain }
+        } else {
+            TableCell { text: time.format(), style: Style::Time(count) }
+        }
+    }
+
+    fn plain(text: String) -> Self {
+        TableCell { text, style: Style::Plain }
+    }
+}
+
+/// Header and whether the column is right-aligned, in the order of `table_row`.
+fn table_columns(pace: Option<Pace>, warnings: bool) -> Vec<(String, bool)> {
+    let m = messages();
+    let mut columns: Vec<(String, bool)> = [m.name, m.main, m.main_extra, m.completionist, m.beat, m.rating, m.year].iter()
+        .enumerate()
+        .map(|(index, header)| (header.to_string(), index > 0))
+        .collect();
+    if let Some(pace) = pace {
+        columns.push((fill(m.at_pace, &[&pace]), true));
+        columns.extend([m.main_story_by, m.main_extra_by, m.completionist_by].map(|header| (header.to_string(), true)));
+    }
+    if warnings {
+        columns.push((m.warnings.to_string(), false));
+    }
+    columns
+}
+
+/// The main story, main + extra and completionist times, zero where nobody submitted one.
+fn submitted_times(game: &Game) -> [Duration; 3] {
+    [(game.comp_main, game.comp_main_count), (game.comp_plus, game.comp_plus_count), (game.comp_100, game.comp_100_count)]
+        .map(|(time, count)| if count == 0 { Duration::ZERO } else { time })
+}
+
+fn table_row(game: &Game, pace: Option<Pace>, warnings: bool) -> Vec<TableCell> {
+    let m = messages();
+    let mut row = vec![
+        TableCell { text: game.game_name.clone(), style: Style::Name },
+        TableCell::time(game.comp_main, game.comp_main_count),
+        TableCell::time(game.comp_plus, game.comp_plus_count),
+        TableCell::time(game.comp_100, game.comp_100_count),
+        TableCell::plain(m.number(game.count_comp)),
+        TableCell::plain(if game.review_score == 0 { m.not_rated.to_string() } else { format!("{}%", game.review_score) }),
+        TableCell::plain(if game.release_world == 0 { String::new() } else { game.release_world.to_string() }),
+    ];
+    if let Some(pace) = pace {
+        let times = submitted_times(game);
+        row.push(TableCell::plain(pace.summary(&times)));
+        let finish = |time: Duration| (!time.is_zero()).then(|| pace.estimate(time).finish).flatten();
+        row.extend(times.map(|time| TableCell::plain(finish(time).map(|finish| m.date(finish)).unwrap_or_else(|| "–".to_string()))));
+    }
+    if warnings {
+        let text = game.warnings().into_iter().map(|warning| m.short_warning(warning)).collect::<Vec<&str>>().join(", ");
+        row.push(TableCell { text, style: Style::Warning });
+    }
+    row
+}
+
+/// Writes a row per game with aligned columns, cutting names so rows fit in
+/// `max_width` terminal columns.
+fn write_table(out: &mut dyn Write, games: &[Game], max_width: Option<usize>, pace: Option<Pace>) -> io::Result<()> {
+    let warnings = games.iter().any(|game| !game.warnings().is_empty());
+    let columns = table_columns(pace, warnings);
+    let rows: Vec<Vec<TableCell>> = games.iter().map(|game| table_row(game, pace, warnings)).collect();
+
+    let mut widths: Vec<usize> = columns.iter().map(|(header, _)| header.width()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.text.width());
+        }
+    }
+    if let Some(max_width) = max_width {
+        let others: usize = widths[1..].iter().sum::<usize>() + SEPARATOR.len() * (widths.len() - 1);
+        widths[0] = widths[0].min(max_width.saturating_sub(others).max(MIN_NAME_WIDTH));
+    }
+
+    let header = columns.iter().zip(&widths)
+        .map(|((header, right_aligned), width)| pad(header, *width, *right_aligned))
+        .collect::<Vec<String>>()
+        .join(SEPARATOR);
+    writeln!(out, "{}", header.trim_end().truecolor(200, 200, 200))?;
+
+    for row in rows {
+        let line = row.iter().zip(columns.iter().zip(&widths))
+            .map(|(cell, ((_, right_aligned), width))| {
+                // pad before coloring, escape codes would throw off the widths
+                let text = pad(&truncate(&cell.text, *width), *width, *right_aligned);
+                match cell.style {
+                    Style::Name => text.bold().to_string(),
+                    Style::Time(count) => format_with_color(&text, count),
+                    Style::Warning => text.yellow().to_string(),
+                    Style::Plain => text,
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(SEPARATOR);
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+/// A game on a single line: its name and times separated by slashes, with
+/// labels for the solo, co-op and versus times of multiplayer games, – for
+/// times nobody has submitted, and ⚠ when the times look suspicious.
+fn compact_line(game: &Game, pace: Option<Pace>) -> String {
+    let multiplayer = game.comp_lvl_combine == 1;
+    let times = time_slots(game).into_iter()
+        .map(|(label, time, count)| {
+            let time = if count == 0 { "–".to_string() } else { format_with_color(&time.format(), count) };
+            if multiplayer { format!("{} {}", label, time) } else { time }
+        })
+        .collect::<Vec<String>>()
+        .join(" / ");
+    let mut line = format!("{} — {}", game.game_name.bold(), times);
+    if let Some(pace) = pace {
+        line = format!("{} · {}", line, pace.summary(&submitted_times(game)));
+    }
+    if !game.warnings().is_empty() {
+        line = format!("{} {}", line, "⚠".yellow());
+    }
+    line
+}
+
+/// The "Found 12 Games, showing 1–5" line above cards and tables, where
+/// `first` is the position of the first game among all results.
+fn write_found(out: &mut dyn Write, page: &SearchPage, first: u64) -> io::Result<()> {
+    let m = messages();
+    write!(out, "{}", m.plural(&m.found_games, page.count as u64))?;
+    if !page.data.is_empty() && (page.data.len() as u32) < page.count {
+        let last = first.saturating_add(page.data.len() as u64 - 1);
+        write!(out, ", {}", fill(m.showing, &[&m.number(first), &m.number(last)]))?;
+    }
+    writeln!(out)
+}
+
+/// A block of lines per game, next to its cover when there are `images`.
+pub(crate) struct CardRenderer {
+    pub(crate) terminal: Terminal,
+    pub(crate) info: bool,
+    pub(crate) pace: Option<Pace>,
+    /// What was searched for, a game named exactly like it is highlighted.
+    pub(crate) terms: String,
+    pub(crate) first: u64,
+    /// Covers in the order of the games, empty to leave them out.
+    pub(crate) images: Vec<DynamicImage>,
+}
+
+impl CardRenderer {
+    fn card_lines(&self, game: &Game) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        let mut name = game.game_name.bold();
+        if self.terms.to_lowercase() == game.game_name.to_lowercase() {
+            name = name.green();
+        }
+        lines.push(name_with_steam_link(name, game.profile_steam, &self.terminal));
+
+        let m = messages();
+        if self.info {
+            lines.push(format!("{} {}", format!("{}:", m.developer).truecolor(200, 200, 200), game.profile_dev));
+
+            let components = [
+                (m.playing, m.number(game.count_playing)),
+                (m.backlogs, m.number(game.count_backlog)),
+                (m.retired, retired_share(game.count_retired, game.count_comp, game.count_backlog)),
+                (m.rating, if game.review_score == 0 { m.not_rated.to_string() } else { format!("{}%", game.review_score) }),
+                (m.beat, m.number(game.count_comp))
+            ]
+                .into_iter()
+                .map(|(label, count)| format!("{} {}", format!("{}:", label).truecolor(200, 200, 200), count))
+                .collect::<Vec<String>>()
+                .join(", ");
+            lines.push(components);
+        }
+
+        lines.extend(time_component_lines(game, self.info, self.pace));
+        for warning in game.warnings() {
+            lines.push(format!("⚠ {}", m.warning(warning)).yellow().to_string());
+        }
+        lines
+    }
+}
+
+impl Renderer for CardRenderer {
+    fn render(&self, out: &mut dyn Write, page: &SearchPage) -> Result<(), CliError> {
+        write_found(out, page, self.first)?;
+        for (index, game) in page.data.iter().enumerate() {
+            writeln!(out)?;
+            let lines = self.card_lines(game);
+            match self.images.get(index) {
+                Some(image) => print_beside_image(out, image, &lines)?,
+                None => for line in lines {
+                    writeln!(out, "{}", line)?;
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A row per game, see `write_table`.
+pub(crate) struct TableRenderer {
+    pub(crate) terminal: Terminal,
+    pub(crate) first: u64,
+    pub(crate) pace: Option<Pace>,
+}
+
+impl Renderer for TableRenderer {
+    fn render(&self, out: &mut dyn Write, page: &SearchPage) -> Result<(), CliError> {
+        write_found(out, page, self.first)?;
+        if !page.data.is_empty() {
+            writeln!(out)?;
+            write_table(out, &page.data, self.terminal.width, self.pace)?;
+        }
+        Ok(())
+    }
+}
+
+/// A line per game, see `compact_line`.
+pub(crate) struct CompactRenderer {
+    pub(crate) pace: Option<Pace>,
+}
+
+impl Renderer for CompactRenderer {
+    fn render(&self, out: &mut dyn Write, page: &SearchPage) -> Result<(), CliError> {
+        for game in &page.data {
+            writeln!(out, "{}", compact_line(game, self.pace))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+//! Unofficial client for searching the [HowLongToBeat](https://howlongtobeat.com) database.
+//!
+//! ```no_run
+//! use howlongtobeat::{HltbClient, SearchQuery, Platform};
+//!
+//! # async fn run() -> howlongtobeat::Result<()> {
+//! let client = HltbClient::new()?;
+//! let query = SearchQuery { platform: Platform::PC, ..SearchQuery::new(["The Witcher 3"]) };
+//! for game in client.search(&query).await?.data {
+//!     println!("{}", game.game_name);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod cache_file;
+mod client;
+pub mod discovery;
+mod error;
+pub mod game_page;
+mod hash_cache;
+pub mod post_body;
+pub mod post_result;
+pub mod quality;
+pub mod query;
+mod rate_limit;
+mod response_cache;
+mod retry;
+pub mod schema;
+
+pub use client::{HltbClient, HltbClientBuilder, DEFAULT_BASE_URL, DEFAULT_RATE_LIMIT, DEFAULT_USER_AGENT};
+pub use discovery::Discovery;
+pub use error::{Error, Result};
+pub use game_page::{GameDetails, TimeCategory, TimeStats};
+pub use hash_cache::{CachedHash, HashCache};
+pub use post_body::Range;
+pub use post_result::Game;
+pub use quality::Warning;
+pub use query::{DlcFilter, Flow, Genre, Perspective, Platform, SearchQuery, SortCategory};
+pub use rate_limit::RateLimiter;
+pub use response_cache::{CacheMode, ResponseCache};
+pub use retry::{Retry, RetryPolicy};
+pub use schema::SCHEMA_VERSION;
+
+/// A single page of search results.
+pub type SearchPage = post_result::PostResult;
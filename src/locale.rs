@@ -0,0 +1,813 @@
+use std::fmt::Display;
+use std::sync::OnceLock;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use howlongtobeat::Warning;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum Lang {
+    /// English
+    #[default]
+    En,
+    /// German
+    De,
+    /// Polish
+    Pl,
+    /// French
+    Fr,
+    /// Spanish
+    Es,
+}
+
+impl Lang {
+    /// The language of the POSIX locale in `LC_ALL`, `LC_MESSAGES` or `LANG`,
+    /// whichever is set first, e.g. German for `de_DE.UTF-8`.
+    pub(crate) fn from_env() -> Option<Lang> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Lang::from_locale(&locale))
+    }
+
+    fn from_locale(locale: &str) -> Option<Lang> {
+        let language = locale.split(['_', '-', '.', '@']).next().unwrap_or_default();
+        Lang::from_str(language, true).ok()
+    }
+
+    fn messages(&self) -> &'static Messages {
+        match self {
+            Lang::En => &EN,
+            Lang::De => &DE,
+            Lang::Pl => &PL,
+            Lang::Fr => &FR,
+            Lang::Es => &ES,
+        }
+    }
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Sets the language of every label and message, once at startup.
+pub(crate) fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+/// The messages in the chosen language, English until one is set.
+pub(crate) fn messages() -> &'static Messages {
+    LANG.get().copied().unwrap_or_default().messages()
+}
+
+/// Fills each `{}` in `template` with the next of `args`.
+pub(crate) fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut args = args.iter();
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            filled.push_str(&arg.to_string());
+        }
+        filled.push_str(part);
+    }
+    filled
+}
+
+/// The forms of a message that depends on a number, each with a `{}` for it.
+/// Only Polish tells `few` (2–4, 22–24, …) apart from `many`.
+pub(crate) struct Plural {
+    one: &'static str,
+    few: &'static str,
+    many: &'static str,
+}
+
+/// Every label and message, in one language.
+pub(crate) struct Messages {
+    lang: Lang,
+    thousands_separator: &'static str,
+    /// Numbers with fewer digits aren't grouped, e.g. 1234 in Polish and Spanish.
+    min_grouping_digits: usize,
+    pub(crate) decimal_separator: &'static str,
+    date_format: &'static str,
+    /// The units of `--time-format hltb`, e.g. 22½ Hours and 45 Mins.
+    pub(crate) hltb_hours: Plural,
+    pub(crate) hltb_mins: Plural,
+
+    pub(crate) main_story: &'static str,
+    /// The main story in tables, where space is short.
+    pub(crate) main: &'static str,
+    pub(crate) main_extra: &'static str,
+    /// Main + extra the way the game page names it, in `game` and `compare`.
+    pub(crate) main_extras: &'static str,
+    pub(crate) completionist: &'static str,
+    pub(crate) all_styles: &'static str,
+    pub(crate) solo: &'static str,
+    pub(crate) coop: &'static str,
+    /// Co-op the way the game page names it, in `game`.
+    pub(crate) co_op: &'static str,
+    pub(crate) versus: &'static str,
+    /// Versus on cards, before the colon.
+    pub(crate) versus_card: &'static str,
+    pub(crate) competitive: &'static str,
+
+    pub(crate) developer: &'static str,
+    pub(crate) publisher: &'static str,
+    pub(crate) genres: &'static str,
+    pub(crate) platforms: &'static str,
+    pub(crate) released: &'static str,
+    pub(crate) playing: &'static str,
+    pub(crate) backlogs: &'static str,
+    pub(crate) retired: &'static str,
+    pub(crate) rating: &'static str,
+    pub(crate) beat: &'static str,
+    pub(crate) not_rated: &'static str,
+
+    pub(crate) found_games: Plural,
+    pub(crate) showing: &'static str,
+    pub(crate) name: &'static str,
+    pub(crate) year: &'static str,
+    pub(crate) at_pace: &'static str,
+    pub(crate) main_story_by: &'static str,
+    pub(crate) main_extra_by: &'static str,
+    pub(crate) completionist_by: &'static str,
+    pub(crate) warnings: &'static str,
+
+    pub(crate) polled: &'static str,
+    pub(crate) average: &'static str,
+    pub(crate) median: &'static str,
+    pub(crate) rushed: &'static str,
+    pub(crate) leisure: &'static str,
+    pub(crate) no_times: &'static str,
+
+    pub(crate) day: &'static str,
+    pub(crate) week: &'static str,
+    pub(crate) days: Plural,
+    pub(crate) weeks: Plural,
+    pub(crate) finish_by: &'static str,
+
+    pub(crate) extras_shorter_than_main: &'static str,
+    pub(crate) completionist_shorter_than_extras: &'static str,
+    pub(crate) single_completionist_submission: &'static str,
+    pub(crate) large_gap: &'static str,
+    pub(crate) extras_shorter_than_main_short: &'static str,
+    pub(crate) completionist_shorter_than_extras_short: &'static str,
+    pub(crate) single_completionist_submission_short: &'static str,
+    pub(crate) large_gap_short: &'static str,
+
+    pub(crate) caching_disabled: &'static str,
+    pub(crate) search_hash_cache: &'static str,
+    pub(crate) response_cache: &'static str,
+    pub(crate) hash_entry: &'static str,
+    pub(crate) fresh: &'static str,
+    pub(crate) expired: &'static str,
+    pub(crate) empty: &'static str,
+    pub(crate) responses: Plural,
+    pub(crate) fresh_for: &'static str,
+    pub(crate) minutes: Plural,
+    pub(crate) cache_cleared: &'static str,
+
+    pub(crate) settings_from: &'static str,
+    pub(crate) settings_from_profile: &'static str,
+    pub(crate) no_config_file: &'static str,
+    pub(crate) from_default: &'static str,
+    pub(crate) from_config: &'static str,
+    pub(crate) from_profile: &'static str,
+    pub(crate) from_saved_search: &'static str,
+    pub(crate) from_environment: &'static str,
+    pub(crate) from_command_line: &'static str,
+    pub(crate) search_saved: &'static str,
+    pub(crate) search_deleted: &'static str,
+    pub(crate) no_saved_searches: &'static str,
+
+    pub(crate) game: &'static str,
+    pub(crate) steam: &'static str,
+    pub(crate) store: &'static str,
+    pub(crate) no_games: &'static str,
+    pub(crate) submissions: Plural,
+}
+
+impl Messages {
+    /// The form of `plural` that goes with `count`, with the count filled in.
+    pub(crate) fn plural(&self, plural: &Plural, count: u64) -> String {
+        fill(self.plural_form(plural, count), &[&self.number(count)])
+    }
+
+    /// The form of `plural` that goes with `count`, still with its `{}`.
+    pub(crate) fn plural_form(&self, plural: &Plural, count: u64) -> &'static str {
+        match self.lang {
+            Lang::En | Lang::De | Lang::Es => if count == 1 { plural.one } else { plural.many },
+            Lang::Fr => if count <= 1 { plural.one } else { plural.many },
+            Lang::Pl => match (count % 10, count % 100) {
+                _ if count == 1 => plural.one,
+                (2..=4, tens) if !(12..=14).contains(&tens) => plural.few,
+                _ => plural.many,
+            },
+        }
+    }
+
+    /// A whole number with thousands separators, like 12,345.
+    pub(crate) fn number(&self, number: impl Into<u64>) -> String {
+        let digits = number.into().to_string();
+        if digits.len() < self.min_grouping_digits { return digits }
+
+        let mut grouped = String::new();
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index).is_multiple_of(3) {
+                grouped.push_str(self.thousands_separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    /// A number with `decimals` digits after the decimal separator, like 12.5.
+    pub(crate) fn decimal(&self, number: f64, decimals: usize) -> String {
+        let formatted = format!("{:.decimals$}", number);
+        match formatted.split_once('.') {
+            Some((whole, fraction)) => format!("{}{}{}", whole, self.decimal_separator, fraction),
+            None => formatted,
+        }
+    }
+
+    pub(crate) fn date(&self, date: NaiveDate) -> String {
+        date.format(self.date_format).to_string()
+    }
+
+    pub(crate) fn warning(&self, warning: Warning) -> &'static str {
+        match warning {
+            Warning::ExtrasShorterThanMain => self.extras_shorter_than_main,
+            Warning::CompletionistShorterThanExtras => self.completionist_shorter_than_extras,
+            Warning::SingleCompletionistSubmission => self.single_completionist_submission,
+            Warning::LargeGap => self.large_gap,
+        }
+    }
+
+    /// A few words about a warning, short enough for a table.
+    pub(crate) fn short_warning(&self, warning: Warning) -> &'static str {
+        match warning {
+            Warning::ExtrasShorterThanMain => self.extras_shorter_than_main_short,
+            Warning::CompletionistShorterThanExtras => self.completionist_shorter_than_extras_short,
+            Warning::SingleCompletionistSubmission => self.single_completionist_submission_short,
+            Warning::LargeGap => self.large_gap_short,
+        }
+    }
+}
+
+static EN: Messages = Messages {
+    lang: Lang::En,
+    thousands_separator: ",",
+    min_grouping_digits: 4,
+    decimal_separator: ".",
+    date_format: "%Y-%m-%d",
+    hltb_hours: Plural { one: "{} Hour", few: "{} Hours", many: "{} Hours" },
+    hltb_mins: Plural { one: "{} Mins", few: "{} Mins", many: "{} Mins" },
+
+    main_story: "Main Story",
+    main: "Main",
+    main_extra: "Main + Extra",
+    main_extras: "Main + Extras",
+    completionist: "Completionist",
+    all_styles: "All PlayStyles",
+    solo: "Solo",
+    coop: "Co-op",
+    co_op: "Co-Op",
+    versus: "Vs.",
+    versus_card: "Vs.  ",
+    competitive: "Competitive",
+
+    developer: "Developer",
+    publisher: "Publisher",
+    genres: "Genres",
+    platforms: "Platforms",
+    released: "Released",
+    playing: "Playing",
+    backlogs: "Backlogs",
+    retired: "Retired",
+    rating: "Rating",
+    beat: "Beat",
+    not_rated: "NR",
+
+    found_games: Plural { one: "Found {} Game", few: "Found {} Games", many: "Found {} Games" },
+    showing: "showing {}–{}",
+    name: "Name",
+    year: "Year",
+    at_pace: "At {}",
+    main_story_by: "Main Story by",
+    main_extra_by: "Main + Extra by",
+    completionist_by: "Completionist by",
+    warnings: "Warnings",
+
+    polled: "Polled",
+    average: "Average",
+    median: "Median",
+    rushed: "Rushed",
+    leisure: "Leisure",
+    no_times: "No completion times submitted yet",
+
+    day: "day",
+    week: "week",
+    days: Plural { one: "{} day", few: "{} days", many: "{} days" },
+    weeks: Plural { one: "{} week", few: "{} weeks", many: "{} weeks" },
+    finish_by: "{}, by {}",
+
+    extras_shorter_than_main: "Main + Extra is shorter than Main Story",
+    completionist_shorter_than_extras: "Completionist is shorter than Main + Extra",
+    single_completionist_submission: "Completionist time comes from a single submission",
+    large_gap: "Unusually large gap between categories",
+    extras_shorter_than_main_short: "extra < main",
+    completionist_shorter_than_extras_short: "100% < extra",
+    single_completionist_submission_short: "1 poll for 100%",
+    large_gap_short: "large gap",
+
+    caching_disabled: "Caching is disabled",
+    search_hash_cache: "Search hash cache",
+    response_cache: "Response cache",
+    hash_entry: "{} for {}, discovered {} ago ({})",
+    fresh: "fresh",
+    expired: "expired",
+    empty: "empty",
+    responses: Plural { one: "{} response", few: "{} responses", many: "{} responses" },
+    fresh_for: "{}, fresh for {}",
+    minutes: Plural { one: "{} minute", few: "{} minutes", many: "{} minutes" },
+    cache_cleared: "Cache cleared",
+
+    settings_from: "Settings from {}",
+    settings_from_profile: "Settings from {}, profile {}",
+    no_config_file: "No config file at {}",
+    from_default: "default",
+    from_config: "config file",
+    from_profile: "profile {}",
+    from_saved_search: "saved search {}",
+    from_environment: "environment",
+    from_command_line: "command line",
+    search_saved: "Saved search {} in {}",
+    search_deleted: "Deleted saved search {}",
+    no_saved_searches: "No saved searches in {}",
+
+    game: "Game",
+    steam: "Steam",
+    store: "Store",
+    no_games: "No games found.",
+    submissions: Plural { one: "{} submission", few: "{} submissions", many: "{} submissions" },
+};
+
+static DE: Messages = Messages {
+    lang: Lang::De,
+    thousands_separator: ".",
+    min_grouping_digits: 4,
+    decimal_separator: ",",
+    date_format: "%d.%m.%Y",
+    hltb_hours: Plural { one: "{} Stunde", few: "{} Stunden", many: "{} Stunden" },
+    hltb_mins: Plural { one: "{} Min", few: "{} Min", many: "{} Min" },
+
+    main_story: "Hauptgeschichte",
+    main: "Haupt",
+    main_extra: "Haupt + Extras",
+    main_extras: "Haupt + Extras",
+    completionist: "Komplettist",
+    all_styles: "Alle Spielstile",
+    solo: "Solo",
+    coop: "Koop",
+    co_op: "Koop",
+    versus: "Vs.",
+    versus_card: "Vs.",
+    competitive: "Kompetitiv",
+
+    developer: "Entwickler",
+    publisher: "Publisher",
+    genres: "Genres",
+    platforms: "Plattformen",
+    released: "Erschienen",
+    playing: "Spielen gerade",
+    backlogs: "Backlogs",
+    retired: "Abgebrochen",
+    rating: "Bewertung",
+    beat: "Durchgespielt",
+    not_rated: "k. A.",
+
+    found_games: Plural { one: "{} Spiel gefunden", few: "{} Spiele gefunden", many: "{} Spiele gefunden" },
+    showing: "angezeigt: {}–{}",
+    name: "Name",
+    year: "Jahr",
+    at_pace: "Bei {}",
+    main_story_by: "Hauptgeschichte bis",
+    main_extra_by: "Haupt + Extras bis",
+    completionist_by: "Komplettist bis",
+    warnings: "Warnungen",
+
+    polled: "Einträge",
+    average: "Schnitt",
+    median: "Median",
+    rushed: "Schnell",
+    leisure: "Gemütlich",
+    no_times: "Noch keine Spielzeiten eingetragen",
+
+    day: "Tag",
+    week: "Woche",
+    days: Plural { one: "{} Tag", few: "{} Tage", many: "{} Tage" },
+    weeks: Plural { one: "{} Woche", few: "{} Wochen", many: "{} Wochen" },
+    finish_by: "{}, bis {}",
+
+    extras_shorter_than_main: "Haupt + Extras ist kürzer als die Hauptgeschichte",
+    completionist_shorter_than_extras: "Komplettist ist kürzer als Haupt + Extras",
+    single_completionist_submission: "Die Komplettist-Zeit beruht auf einem einzigen Eintrag",
+    large_gap: "Ungewöhnlich großer Abstand zwischen den Kategorien",
+    extras_shorter_than_main_short: "Extras < Haupt",
+    completionist_shorter_than_extras_short: "100 % < Extras",
+    single_completionist_submission_short: "1 Eintrag für 100 %",
+    large_gap_short: "großer Abstand",
+
+    caching_disabled: "Caching ist deaktiviert",
+    search_hash_cache: "Such-Hash-Cache",
+    response_cache: "Antwort-Cache",
+    hash_entry: "{} für {}, ermittelt vor {} ({})",
+    fresh: "gültig",
+    expired: "abgelaufen",
+    empty: "leer",
+    responses: Plural { one: "{} Antwort", few: "{} Antworten", many: "{} Antworten" },
+    fresh_for: "{}, gültig für {}",
+    minutes: Plural { one: "{} Minute", few: "{} Minuten", many: "{} Minuten" },
+    cache_cleared: "Cache geleert",
+
+    settings_from: "Einstellungen aus {}",
+    settings_from_profile: "Einstellungen aus {}, Profil {}",
+    no_config_file: "Keine Konfigurationsdatei unter {}",
+    from_default: "Standard",
+    from_config: "Konfigurationsdatei",
+    from_profile: "Profil {}",
+    from_saved_search: "gespeicherte Suche {}",
+    from_environment: "Umgebung",
+    from_command_line: "Befehlszeile",
+    search_saved: "Suche {} in {} gespeichert",
+    search_deleted: "Gespeicherte Suche {} gelöscht",
+    no_saved_searches: "Keine gespeicherten Suchen in {}",
+
+    game: "Spiel",
+    steam: "Steam",
+    store: "Store",
+    no_games: "Keine Spiele gefunden.",
+    submissions: Plural { one: "{} Eintrag", few: "{} Einträge", many: "{} Einträge" },
+};
+
+static PL: Messages = Messages {
+    lang: Lang::Pl,
+    thousands_separator: "\u{a0}",
+    min_grouping_digits: 5,
+    decimal_separator: ",",
+    date_format: "%d.%m.%Y",
+    hltb_hours: Plural { one: "{} godzina", few: "{} godziny", many: "{} godzin" },
+    hltb_mins: Plural { one: "{} min", few: "{} min", many: "{} min" },
+
+    main_story: "Fabuła",
+    main: "Fabuła",
+    main_extra: "Fabuła + dodatki",
+    main_extras: "Fabuła + dodatki",
+    completionist: "Na 100%",
+    all_styles: "Wszystkie style",
+    solo: "Solo",
+    coop: "Co-op",
+    co_op: "Co-op",
+    versus: "Vs.",
+    versus_card: "Vs.",
+    competitive: "Rywalizacja",
+
+    developer: "Producent",
+    publisher: "Wydawca",
+    genres: "Gatunki",
+    platforms: "Platformy",
+    released: "Premiera",
+    playing: "Grają",
+    backlogs: "W kolejce",
+    retired: "Porzucone",
+    rating: "Ocena",
+    beat: "Ukończone",
+    not_rated: "brak",
+
+    found_games: Plural { one: "Znaleziono {} grę", few: "Znaleziono {} gry", many: "Znaleziono {} gier" },
+    showing: "wyświetlono {}–{}",
+    name: "Nazwa",
+    year: "Rok",
+    at_pace: "Przy {}",
+    main_story_by: "Fabuła do",
+    main_extra_by: "Fabuła + dodatki do",
+    completionist_by: "Na 100% do",
+    warnings: "Ostrzeżenia",
+
+    polled: "Głosy",
+    average: "Średnia",
+    median: "Mediana",
+    rushed: "Szybko",
+    leisure: "Spokojnie",
+    no_times: "Nikt jeszcze nie podał czasu ukończenia",
+
+    day: "dzień",
+    week: "tydzień",
+    days: Plural { one: "{} dzień", few: "{} dni", many: "{} dni" },
+    weeks: Plural { one: "{} tydzień", few: "{} tygodnie", many: "{} tygodni" },
+    finish_by: "{}, do {}",
+
+    extras_shorter_than_main: "Fabuła + dodatki trwa krócej niż sama fabuła",
+    completionist_shorter_than_extras: "Na 100% trwa krócej niż fabuła + dodatki",
+    single_completionist_submission: "Czas na 100% pochodzi z jednego zgłoszenia",
+    large_gap: "Nietypowo duża różnica między kategoriami",
+    extras_shorter_than_main_short: "dodatki < fabuła",
+    completionist_shorter_than_extras_short: "100% < dodatki",
+    single_completionist_submission_short: "1 głos na 100%",
+    large_gap_short: "duża różnica",
+
+    caching_disabled: "Pamięć podręczna jest wyłączona",
+    search_hash_cache: "Pamięć hasha wyszukiwania",
+    response_cache: "Pamięć odpowiedzi",
+    hash_entry: "{} dla {}, wykryty {} temu ({})",
+    fresh: "aktualny",
+    expired: "nieaktualny",
+    empty: "pusta",
+    responses: Plural { one: "{} odpowiedź", few: "{} odpowiedzi", many: "{} odpowiedzi" },
+    fresh_for: "{}, aktualne przez {}",
+    minutes: Plural { one: "{} minutę", few: "{} minuty", many: "{} minut" },
+    cache_cleared: "Pamięć podręczna wyczyszczona",
+
+    settings_from: "Ustawienia z {}",
+    settings_from_profile: "Ustawienia z {}, profil {}",
+    no_config_file: "Brak pliku konfiguracji {}",
+    from_default: "domyślne",
+    from_config: "plik konfiguracji",
+    from_profile: "profil {}",
+    from_saved_search: "zapisane wyszukiwanie {}",
+    from_environment: "zmienna środowiskowa",
+    from_command_line: "wiersz poleceń",
+    search_saved: "Zapisano wyszukiwanie {} w {}",
+    search_deleted: "Usunięto zapisane wyszukiwanie {}",
+    no_saved_searches: "Brak zapisanych wyszukiwań w {}",
+
+    game: "Gra",
+    steam: "Steam",
+    store: "Sklep",
+    no_games: "Nie znaleziono gier.",
+    submissions: Plural { one: "{} zgłoszenie", few: "{} zgłoszenia", many: "{} zgłoszeń" },
+};
+
+static FR: Messages = Messages {
+    lang: Lang::Fr,
+    thousands_separator: "\u{202f}",
+    min_grouping_digits: 4,
+    decimal_separator: ",",
+    date_format: "%d/%m/%Y",
+    hltb_hours: Plural { one: "{} heure", few: "{} heures", many: "{} heures" },
+    hltb_mins: Plural { one: "{} min", few: "{} min", many: "{} min" },
+
+    main_story: "Histoire",
+    main: "Histoire",
+    main_extra: "Histoire + extras",
+    main_extras: "Histoire + extras",
+    completionist: "Complétionniste",
+    all_styles: "Tous styles",
+    solo: "Solo",
+    coop: "Coop",
+    co_op: "Coop",
+    versus: "Vs.",
+    versus_card: "Vs.",
+    competitive: "Compétitif",
+
+    developer: "Développeur",
+    publisher: "Éditeur",
+    genres: "Genres",
+    platforms: "Plateformes",
+    released: "Sortie",
+    playing: "En cours",
+    backlogs: "À jouer",
+    retired: "Abandonné",
+    rating: "Note",
+    beat: "Terminé",
+    not_rated: "NN",
+
+    found_games: Plural { one: "{} jeu trouvé", few: "{} jeux trouvés", many: "{} jeux trouvés" },
+    showing: "affichés {}–{}",
+    name: "Nom",
+    year: "Année",
+    at_pace: "À {}",
+    main_story_by: "Histoire finie le",
+    main_extra_by: "Histoire + extras finie le",
+    completionist_by: "Complétionniste le",
+    warnings: "Avertissements",
+
+    polled: "Votes",
+    average: "Moyenne",
+    median: "Médiane",
+    rushed: "Rapide",
+    leisure: "Tranquille",
+    no_times: "Aucun temps soumis pour l'instant",
+
+    day: "jour",
+    week: "semaine",
+    days: Plural { one: "{} jour", few: "{} jours", many: "{} jours" },
+    weeks: Plural { one: "{} semaine", few: "{} semaines", many: "{} semaines" },
+    finish_by: "{}, d'ici le {}",
+
+    extras_shorter_than_main: "Histoire + extras est plus court que l'histoire seule",
+    completionist_shorter_than_extras: "Complétionniste est plus court que histoire + extras",
+    single_completionist_submission: "Le temps complétionniste repose sur une seule soumission",
+    large_gap: "Écart inhabituellement grand entre les catégories",
+    extras_shorter_than_main_short: "extras < histoire",
+    completionist_shorter_than_extras_short: "100 % < extras",
+    single_completionist_submission_short: "1 vote pour 100 %",
+    large_gap_short: "grand écart",
+
+    caching_disabled: "Le cache est désactivé",
+    search_hash_cache: "Cache du hash de recherche",
+    response_cache: "Cache des réponses",
+    hash_entry: "{} pour {}, découvert il y a {} ({})",
+    fresh: "valide",
+    expired: "expiré",
+    empty: "vide",
+    responses: Plural { one: "{} réponse", few: "{} réponses", many: "{} réponses" },
+    fresh_for: "{}, valides pendant {}",
+    minutes: Plural { one: "{} minute", few: "{} minutes", many: "{} minutes" },
+    cache_cleared: "Cache vidé",
+
+    settings_from: "Réglages de {}",
+    settings_from_profile: "Réglages de {}, profil {}",
+    no_config_file: "Aucun fichier de configuration à {}",
+    from_default: "par défaut",
+    from_config: "fichier de configuration",
+    from_profile: "profil {}",
+    from_saved_search: "recherche enregistrée {}",
+    from_environment: "environnement",
+    from_command_line: "ligne de commande",
+    search_saved: "Recherche {} enregistrée dans {}",
+    search_deleted: "Recherche enregistrée {} supprimée",
+    no_saved_searches: "Aucune recherche enregistrée dans {}",
+
+    game: "Jeu",
+    steam: "Steam",
+    store: "Boutique",
+    no_games: "Aucun jeu trouvé.",
+    submissions: Plural { one: "{} soumission", few: "{} soumissions", many: "{} soumissions" },
+};
+
+static ES: Messages = Messages {
+    lang: Lang::Es,
+    thousands_separator: ".",
+    min_grouping_digits: 5,
+    decimal_separator: ",",
+    date_format: "%d/%m/%Y",
+    hltb_hours: Plural { one: "{} hora", few: "{} horas", many: "{} horas" },
+    hltb_mins: Plural { one: "{} min", few: "{} min", many: "{} min" },
+
+    main_story: "Historia",
+    main: "Historia",
+    main_extra: "Historia + extras",
+    main_extras: "Historia + extras",
+    completionist: "Completista",
+    all_styles: "Todos los estilos",
+    solo: "Solo",
+    coop: "Cooperativo",
+    co_op: "Cooperativo",
+    versus: "Vs.",
+    versus_card: "Vs.",
+    competitive: "Competitivo",
+
+    developer: "Desarrollador",
+    publisher: "Editor",
+    genres: "Géneros",
+    platforms: "Plataformas",
+    released: "Lanzamiento",
+    playing: "Jugando",
+    backlogs: "Pendientes",
+    retired: "Abandonado",
+    rating: "Nota",
+    beat: "Completado",
+    not_rated: "SN",
+
+    found_games: Plural { one: "{} juego encontrado", few: "{} juegos encontrados", many: "{} juegos encontrados" },
+    showing: "mostrando {}–{}",
+    name: "Nombre",
+    year: "Año",
+    at_pace: "A {}",
+    main_story_by: "Historia para el",
+    main_extra_by: "Historia + extras para el",
+    completionist_by: "Completista para el",
+    warnings: "Avisos",
+
+    polled: "Votos",
+    average: "Media",
+    median: "Mediana",
+    rushed: "Rápido",
+    leisure: "Tranquilo",
+    no_times: "Todavía nadie ha enviado tiempos",
+
+    day: "día",
+    week: "semana",
+    days: Plural { one: "{} día", few: "{} días", many: "{} días" },
+    weeks: Plural { one: "{} semana", few: "{} semanas", many: "{} semanas" },
+    finish_by: "{}, para el {}",
+
+    extras_shorter_than_main: "Historia + extras dura menos que la historia",
+    completionist_shorter_than_extras: "Completista dura menos que historia + extras",
+    single_completionist_submission: "El tiempo de completista se basa en un solo envío",
+    large_gap: "Diferencia inusualmente grande entre categorías",
+    extras_shorter_than_main_short: "extras < historia",
+    completionist_shorter_than_extras_short: "100 % < extras",
+    single_completionist_submission_short: "1 voto para 100 %",
+    large_gap_short: "gran diferencia",
+
+    caching_disabled: "La caché está desactivada",
+    search_hash_cache: "Caché del hash de búsqueda",
+    response_cache: "Caché de respuestas",
+    hash_entry: "{} para {}, descubierto hace {} ({})",
+    fresh: "vigente",
+    expired: "caducado",
+    empty: "vacía",
+    responses: Plural { one: "{} respuesta", few: "{} respuestas", many: "{} respuestas" },
+    fresh_for: "{}, vigentes durante {}",
+    minutes: Plural { one: "{} minuto", few: "{} minutos", many: "{} minutos" },
+    cache_cleared: "Caché vaciada",
+
+    settings_from: "Ajustes de {}",
+    settings_from_profile: "Ajustes de {}, perfil {}",
+    no_config_file: "No hay archivo de configuración en {}",
+    from_default: "predeterminado",
+    from_config: "archivo de configuración",
+    from_profile: "perfil {}",
+    from_saved_search: "búsqueda guardada {}",
+    from_environment: "entorno",
+    from_command_line: "línea de comandos",
+    search_saved: "Búsqueda {} guardada en {}",
+    search_deleted: "Búsqueda guardada {} eliminada",
+    no_saved_searches: "No hay búsquedas guardadas en {}",
+
+    game: "Juego",
+    steam: "Steam",
+    store: "Tienda",
+    no_games: "No se encontraron juegos.",
+    submissions: Plural { one: "{} envío", few: "{} envíos", many: "{} envíos" },
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNTS: [u64; 10] = [0, 1, 2, 4, 5, 12, 22, 111, 1_000_002, u64::MAX];
+
+    fn forms(lang: Lang) -> Vec<String> {
+        let m = lang.messages();
+        COUNTS.iter().map(|count| m.plural(&m.weeks, *count)).collect()
+    }
+
+    #[test]
+    fn english_has_one_and_many() {
+        assert_eq!(forms(Lang::En), [
+            "0 weeks", "1 week", "2 weeks", "4 weeks", "5 weeks", "12 weeks", "22 weeks", "111 weeks",
+            "1,000,002 weeks", "18,446,744,073,709,551,615 weeks",
+        ]);
+    }
+
+    #[test]
+    fn polish_tells_few_from_many() {
+        assert_eq!(forms(Lang::Pl), [
+            "0 tygodni", "1 tydzień", "2 tygodnie", "4 tygodnie", "5 tygodni", "12 tygodni", "22 tygodnie", "111 tygodni",
+            "1\u{a0}000\u{a0}002 tygodnie", "18\u{a0}446\u{a0}744\u{a0}073\u{a0}709\u{a0}551\u{a0}615 tygodni",
+        ]);
+    }
+
+    #[test]
+    fn french_counts_zero_as_one() {
+        let m = Lang::Fr.messages();
+        assert_eq!(m.plural(&m.days, 0), "0 jour");
+        assert_eq!(m.plural(&m.days, 1), "1 jour");
+        assert_eq!(m.plural(&m.days, 2), "2 jours");
+        assert_eq!(m.plural(&m.days, 1234), "1\u{202f}234 jours");
+    }
+
+    #[test]
+    fn numbers_are_grouped_from_the_minimum_digits() {
+        assert_eq!(Lang::En.messages().number(999u64), "999");
+        assert_eq!(Lang::En.messages().number(1234u64), "1,234");
+        assert_eq!(Lang::De.messages().number(1234u64), "1.234");
+        assert_eq!(Lang::Pl.messages().number(1234u64), "1234");
+        assert_eq!(Lang::Es.messages().number(12345u64), "12.345");
+    }
+
+    #[test]
+    fn decimals_use_the_separator() {
+        assert_eq!(Lang::En.messages().decimal(12.5, 1), "12.5");
+        assert_eq!(Lang::Fr.messages().decimal(12.5, 2), "12,50");
+        assert_eq!(Lang::De.messages().decimal(0.0, 0), "0");
+    }
+
+    #[test]
+    fn fill_replaces_each_placeholder_in_turn() {
+        assert_eq!(fill("{} – {}", &[&1, &"zwei"]), "1 – zwei");
+        assert_eq!(fill("{}, d'ici le {}", &[&"3 jours"]), "3 jours, d'ici le ");
+        assert_eq!(fill("no placeholders", &[&1]), "no placeholders");
+        assert_eq!(fill("", &[]), "");
+    }
+
+    #[test]
+    fn locales_name_their_language_first() {
+        assert_eq!(Lang::from_locale("pl_PL.UTF-8"), Some(Lang::Pl));
+        assert_eq!(Lang::from_locale("fr-CA"), Some(Lang::Fr));
+        assert_eq!(Lang::from_locale("DE"), Some(Lang::De));
+        assert_eq!(Lang::from_locale("es@euro"), Some(Lang::Es));
+        assert_eq!(Lang::from_locale("C"), None);
+        assert_eq!(Lang::from_locale(""), None);
+    }
+}
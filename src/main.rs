@@ -1,213 +1,108 @@
-mod post_result;
-mod post_body;
-mod duration_formatter;
-mod display_time_components;
-mod args;
-mod get_search_hash;
-
-use post_result::PostResult;
-use post_body::{Body, SearchOptions, Games, Gameplay};
-use display_time_components::display_time_components;
-use args::{Args, ToggleOption};
-use get_search_hash::get_search_hash;
-
-use reqwest::{Client, ClientBuilder};
-use clap::Parser;
-use colored::{ColoredString, Colorize};
-use viuer::Config;
-use image::DynamicImage;
-use futures::future::join_all;
-
 macro_rules! link {
     ($url:expr, $text:expr) => {
         format!("\x1B]8;;{}\x1B\\{}\x1B]8;;\x1B\\", $url, $text)
     };
 }
 
-fn get_terminal_image_dimensions(image: &DynamicImage) -> (u32, u32) {
-    let ratio = image.width() as f32 / image.height() as f32;
-    let width: u32;
-    let height: u32;
-    if ratio > 1.0 {
-        height = 7u32;
-        width = (height as f32 * ratio) as u32;
-    } else {
-        width = 10u32;
-        height = (width as f32 / ratio) as u32;
-    }
-    (width, (height as f32 / 2.15).round() as u32)
-}
-
-async fn fetch_images(client: Client, urls: Vec<String>) -> Vec<DynamicImage> {
-    let futures = urls.into_iter().map(|url| {
-        let client = client.clone();
-        async move {
-            let image = client.get(url).send().await.unwrap().bytes().await.unwrap();
-            let image = image::load_from_memory(&image).unwrap();
-            let image = DynamicImage::ImageRgba8(image.to_rgba8());
-            image
-        }
-    });
-    join_all(futures).await
-}
+mod duration_formatter;
+mod display_time_components;
+mod display_game_details;
+mod args;
+mod cli_error;
+mod commands;
+mod config;
+mod images;
+mod layout;
+mod locale;
+mod pace;
+mod render;
+mod report;
+mod saved_searches;
+mod tabular;
+mod template;
+
+use args::{Command, GlobalArgs, OutputFormat, SavedSearchAction};
+use cli_error::CliError;
+use config::Layers;
+use render::Output;
+
+use howlongtobeat::schema;
+use colored::Colorize;
+use std::io::{self, Write};
+use std::process::ExitCode;
+use std::time::Duration;
 
 #[tokio::main]
-async fn main() {
-    let mut args = Args::parse();
-
-    let mut new_search = Vec::new();
-    for arg in &args.search {
-        if arg.contains(" ") {
-            let split = arg.split_whitespace();
-            new_search.extend(split.map(|s| s.to_string()));
-        } else {
-            new_search.push(arg.clone());
+async fn main() -> ExitCode {
+    let (cli, layers) = match config::parse() {
+        Ok(parsed) => parsed,
+        Err(e) => return report(e),
+    };
+    let (global, command) = cli.into_command();
+    duration_formatter::set_time_format(global.time_format, global.time_precision);
+    locale::set_lang(global.lang.or_else(locale::Lang::from_env).unwrap_or_default());
+
+    let result = match Output::open(&global) {
+        Ok(mut out) => {
+            colored::control::set_override(out.terminal.color);
+            run(&mut out, &global, &command, &layers).await.and_then(|()| Ok(out.flush()?))
         }
-    }
-    args.search = new_search;
-
-    if args.color == ToggleOption::Never { colored::control::set_override(false) }
-
-    let body = Body {
-        search_type: "games".to_string(),
-        search_terms: args.search.clone(),
-        search_page: 1,
-        size: args.size,
-        search_options: SearchOptions {
-            games: Games {
-                user_id: 0,
-                platform: args.platform.to_string(),
-                sort_category: args.sort.to_string(),
-                range_category: "main".to_string(),
-                range_time: args.range_time.clone(),
-                gameplay: Gameplay {
-                    perspective: args.perspective.to_string(),
-                    flow: args.flow.to_string(),
-                    genre: args.genre.to_string(),
-                },
-                range_year: args.range_year.clone(),
-                modifier: if args.dlc { "only_dlc" } else if args.no_dlc { "hide_dlc" } else { "Modifiers" }.to_string(),
-            },
-            filter: "".to_string(),
-            sort: args.reverse as u8
-        },
-        use_cache: true,
+        Err(e) => Err(e),
     };
 
-    let client = ClientBuilder::new()
-        .default_headers(
-            reqwest::header::HeaderMap::from_iter(
-                vec![
-                    ("User-Agent", "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0"),
-                    ("Referer", "https://howlongtobeat.com"),
-                    ("Content-Type", "application/json"),
-                ].into_iter().map(|(k, v)| (k.parse().unwrap(), v.parse().unwrap()))
-            )
-        )
-        .build().unwrap();
-
-    let search_hash = get_search_hash(&client).await;
-    let res = client
-        .post(format!("https://howlongtobeat.com/api/search/{}", search_hash))
-        .body(serde_json::to_string(&body).unwrap())
-        .send()
-        .await.unwrap().json::<PostResult>().await.unwrap();
-
-    if args.json {
-        println!("{}", serde_json::to_string(&res).unwrap());
-        return;
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // the output was closed early, e.g. piped into `head`
+        Err(CliError::Output(e)) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => report(e),
     }
+}
 
-    let mut images: Vec<DynamicImage> = Vec::new();
-    if args.images {
-        let urls: Vec<String> = res.data.iter().map(|game| format!("https://howlongtobeat.com/games/{}?width=100", game.game_image)).collect();
-        images = fetch_images(client, urls.clone()).await;
-    }
-
-    println!("Found {} Game{}", res.count, if res.count == 1 { "" } else { "s" });
-
-    for (index, game) in res.data.into_iter().enumerate() {
-        println!();
-
-        let mut width: u32 = 0;
-        let mut height: u32 = 0;
-        if args.images {
-            let image = &images[index];
-            (width, height) = get_terminal_image_dimensions(image);
-
-            let conf = Config {
-                absolute_offset: false,
-                width: Some(width),
-                height: Some(height),
-                ..Default::default()
-            };
-
-            viuer::print(image, &conf).unwrap();
-
-            // move cursor to the top of the image
-            print!("\x1B[{}A", height);
-        }
-
-        let mut lines_printed = 0;
+fn report(e: CliError) -> ExitCode {
+    eprintln!("{} {}", "error:".red().bold(), e);
+    e.exit_code()
+}
 
-        let print_indentation_if_images = || {
-            if !args.images { return }
-            // move cursor to the right of the image
-            print!("\x1B[{}C", width + 1);
+async fn run(out: &mut Output, global: &GlobalArgs, command: &Command, layers: &Layers) -> Result<(), CliError> {
+    if global.json_schema {
+        let ndjson = global.format() == OutputFormat::Ndjson;
+        let schema = match command {
+            Command::Search(search) if !matches!(search.saved, None | Some(SavedSearchAction::Run(_))) => {
+                return Err(CliError::Usage("saving, listing and deleting searches has no JSON output".to_string()));
+            }
+            Command::Search(_) if ndjson => schema::search_line_schema(),
+            Command::Search(_) => schema::search_schema(),
+            Command::Game(_) | Command::Compare(_) if ndjson => schema::game_line_schema(),
+            Command::Game(_) => schema::game_schema(),
+            Command::Compare(_) => schema::compare_schema(),
+            Command::Batch(_) if ndjson => schema::batch_line_schema(),
+            Command::Batch(_) => schema::batch_schema(),
+            Command::Cache(_) => return Err(CliError::Usage("the cache command has no JSON output".to_string())),
+            Command::Config(_) => return Err(CliError::Usage("the config command has no JSON output".to_string())),
         };
+        writeln!(out, "{}", serde_json::to_string_pretty(&schema).unwrap())?;
+        return Ok(());
+    }
 
-        macro_rules! println {
-            () => {
-                print_indentation_if_images();
-                print!("\n");
-                lines_printed += 1;
-            };
-            ($($arg:tt)*) => {
-                print_indentation_if_images();
-                print!($($arg)*);
-                print!("\n");
-                lines_printed += 1;
-            };
-        }
-
-        let mut formatted_game_name = game.game_name.bold();
-        if (&args.search).join(" ").to_lowercase() == formatted_game_name.to_lowercase() {
-            formatted_game_name = formatted_game_name.green();
-        }
-
-        if game.profile_steam != 0 {
-            let url = "https://store.steampowered.com/app/".to_owned() + &*game.profile_steam.to_string();
-            let label = "[Steam Store Page]".blue().underline();
-            formatted_game_name = ColoredString::from(format!("{} {}", formatted_game_name, link!(url, label)));
-        }
-
-        println!("{}", formatted_game_name);
-
-        if args.info {
-            println!("{} {}", "Developer:".truecolor(200, 200, 200), game.profile_dev);
-        }
-
-        if args.info {
-            let all_players = game.count_comp + game.count_backlog + game.count_retired;
-            let components = vec![
-                ("Playing:", game.count_playing.to_string()),
-                ("Backlogs:", game.count_backlog.to_string()),
-                ("Retired:", format!("{:0.1}%", game.count_retired as f32 / all_players as f32 * 100.0)),
-                ("Rating:", if game.review_score == 0 { "NR".to_string() } else { format!("{}%", game.review_score) }),
-                ("Beat:", game.count_comp.to_string())
-            ]
-                .into_iter()
-                .map(|(label, count)| format!("{} {}", label.truecolor(200, 200, 200), count))
-                .collect::<Vec<String>>()
-                .join(", ");
-            println!("{}", components);
-        }
-
-        let indentation = if args.images { (width + 1) as u8 } else { 0u8 };
-        lines_printed += display_time_components(indentation, game, &args);
+    let mut client = global.to_client_builder().build()?.with_cache_mode(global.cache_mode());
+    if let Some(ttl) = global.cache_ttl {
+        let response_cache = client.response_cache().clone().with_ttl(Duration::from_secs(ttl));
+        client = client.with_response_cache(response_cache);
+    }
+    if global.verbose {
+        client = client.on_discovery(|discovery| {
+            eprintln!("Discovered search hash {} in {} (strategy: {})", discovery.hash, discovery.script, discovery.strategy);
+        }).on_retry(|retry| {
+            eprintln!("Retrying {} in {:.1}s after {} (attempt {})", retry.url, retry.delay.as_secs_f64(), retry.reason, retry.attempt);
+        });
+    }
 
-        if args.images && height > lines_printed && height - lines_printed > 0 {
-            print!("{}", "\n".repeat((height - lines_printed) as usize));
-        }
+    match command {
+        Command::Search(args) => commands::search::run(out, global, args, &client).await,
+        Command::Game(args) => commands::game::run(out, global, args, &client).await,
+        Command::Compare(args) => commands::compare::run(out, global, args, &client).await,
+        Command::Batch(args) => commands::batch::run(out, global, args, &client).await,
+        Command::Cache(args) => commands::cache::run(out, args, &client),
+        Command::Config(args) => commands::config::run(out, args, layers),
     }
 }
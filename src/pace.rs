@@ -0,0 +1,180 @@
+use std::fmt;
+use std::time::Duration;
+use chrono::{Days, Local, NaiveDate};
+use crate::locale::{fill, messages, Plural};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Period {
+    Day,
+    Week,
+}
+
+impl Period {
+    fn name(&self) -> &'static str {
+        match self {
+            Period::Day => messages().day,
+            Period::Week => messages().week,
+        }
+    }
+
+    fn plural(&self) -> &'static Plural {
+        match self {
+            Period::Day => &messages().days,
+            Period::Week => &messages().weeks,
+        }
+    }
+}
+
+/// How much someone plays, from `--pace`, e.g. `2h/day` or `10h/week`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Pace {
+    hours: f64,
+    period: Period,
+}
+
+/// How long finishing a game takes at some pace.
+pub(crate) struct Estimate {
+    /// Days or weeks of playing, depending on the pace.
+    pub(crate) periods: u64,
+    pub(crate) period: Period,
+    /// The day the game would be finished, none when that's past the last date chrono knows.
+    pub(crate) finish: Option<NaiveDate>,
+}
+
+/// Parses a pace like `2h/day`, `1.5h/day`, `90m/day` or `10h/week`.
+pub(crate) fn parse_pace(s: &str) -> Result<Pace, String> {
+    let (amount, period) = s.split_once('/').ok_or("expected a pace like 2h/day or 10h/week")?;
+    let period = match period.trim().to_lowercase().as_str() {
+        "d" | "day" => Period::Day,
+        "w" | "wk" | "week" => Period::Week,
+        period => return Err(format!("unknown period {:?}, expected day or week", period)),
+    };
+
+    let amount = amount.trim().to_lowercase();
+    let (number, per_hour) = if let Some(minutes) = amount.strip_suffix('m') {
+        (minutes, 60.0)
+    } else {
+        (amount.strip_suffix('h').unwrap_or(&amount), 1.0)
+    };
+    let hours = number.trim().parse::<f64>().map_err(|_| format!("invalid amount of time {:?}", amount))? / per_hour;
+    if !(hours > 0.0 && hours <= if period == Period::Day { 24.0 } else { 168.0 }) {
+        return Err(format!("{} isn't a possible amount of play", s));
+    }
+    Ok(Pace { hours, period })
+}
+
+impl Pace {
+    /// How long playing for `time` takes, counting today as the first day.
+    pub(crate) fn estimate(&self, time: Duration) -> Estimate {
+        let hours = time.as_secs_f64() / 3600.0;
+        let hours_per_day = match self.period {
+            Period::Day => self.hours,
+            Period::Week => self.hours / 7.0,
+        };
+        let days = (hours / hours_per_day).ceil().max(1.0) as u64;
+        let periods = match self.period {
+            Period::Day => days,
+            Period::Week => (hours / self.hours).ceil().max(1.0) as u64,
+        };
+        Estimate { periods, period: self.period, finish: Local::now().date_naive().checked_add_days(Days::new(days - 1)) }
+    }
+
+    /// Days or weeks for each of `times`, like `26 / 52 / 87 days`, with – for
+    /// missing times. The unit agrees with the last number.
+    pub(crate) fn summary(&self, times: &[Duration]) -> String {
+        let periods: Vec<Option<u64>> = times.iter()
+            .map(|time| (!time.is_zero()).then(|| self.estimate(*time).periods))
+            .collect();
+        let list = periods.iter()
+            .map(|periods| periods.map(|periods| messages().number(periods)).unwrap_or_else(|| "–".to_string()))
+            .collect::<Vec<String>>()
+            .join(" / ");
+        let last = periods.last().copied().flatten().unwrap_or(0);
+        fill(messages().plural_form(self.period.plural(), last), &[&list])
+    }
+}
+
+impl fmt::Display for Pace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}h/{}", self.hours.to_string().replace('.', messages().decimal_separator), self.period.name())
+    }
+}
+
+impl Estimate {
+    /// The number of days or weeks, like `26 days`.
+    pub(crate) fn periods(&self) -> String {
+        messages().plural(self.period.plural(), self.periods)
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let finish = self.finish.map(|finish| messages().date(finish)).unwrap_or_else(|| "–".to_string());
+        write!(f, "{}", fill(messages().finish_by, &[&self.periods(), &finish]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3600;
+
+    #[test]
+    fn paces_accept_hours_minutes_and_short_periods() {
+        assert_eq!(parse_pace("2h/day"), Ok(Pace { hours: 2.0, period: Period::Day }));
+        assert_eq!(parse_pace(" 1.5H / Day "), Ok(Pace { hours: 1.5, period: Period::Day }));
+        assert_eq!(parse_pace("90m/d"), Ok(Pace { hours: 1.5, period: Period::Day }));
+        assert_eq!(parse_pace("10/wk"), Ok(Pace { hours: 10.0, period: Period::Week }));
+        assert_eq!(parse_pace("168h/week"), Ok(Pace { hours: 168.0, period: Period::Week }));
+    }
+
+    #[test]
+    fn impossible_paces_are_rejected() {
+        for pace in ["", "2h", "0h/day", "-1h/day", "25h/day", "169h/week", "NaNh/day", "infh/day", "2h/month", "twoh/day"] {
+            assert!(parse_pace(pace).is_err(), "{:?} was accepted", pace);
+        }
+    }
+
+    #[test]
+    fn non_ascii_paces_are_rejected() {
+        for pace in ["２h/day", "2ч/day", "2h/день", "2h/día"] {
+            assert!(parse_pace(pace).is_err(), "{:?} was accepted", pace);
+        }
+    }
+
+    #[test]
+    fn estimates_round_up_to_whole_periods() {
+        let daily = parse_pace("2h/day").unwrap();
+        assert_eq!(daily.estimate(Duration::from_secs(51 * HOUR)).periods, 26);
+        assert_eq!(daily.estimate(Duration::ZERO).periods, 1);
+        assert_eq!(daily.estimate(Duration::ZERO).finish, Some(Local::now().date_naive()));
+
+        let weekly = parse_pace("10h/week").unwrap();
+        let estimate = weekly.estimate(Duration::from_secs(51 * HOUR));
+        assert_eq!((estimate.periods, estimate.period), (6, Period::Week));
+        assert_eq!(estimate.periods(), "6 weeks");
+    }
+
+    #[test]
+    fn summaries_agree_with_the_last_number() {
+        let pace = parse_pace("2h/day").unwrap();
+        let times = [51, 0, 1].map(|hours| Duration::from_secs(hours * HOUR));
+        assert_eq!(pace.summary(&times), "26 / – / 1 day");
+        assert_eq!(pace.summary(&[Duration::from_secs(20_000 * HOUR)]), "10,000 days");
+        assert_eq!(pace.summary(&[Duration::ZERO]), "– days");
+    }
+
+    #[test]
+    fn paces_display_like_they_are_written() {
+        assert_eq!(parse_pace("90m/day").unwrap().to_string(), "1.5h/day");
+        assert_eq!(parse_pace("10h/w").unwrap().to_string(), "10h/week");
+    }
+
+    #[test]
+    fn slow_paces_have_no_finish_date() {
+        let estimate = parse_pace("1e-9h/day").unwrap().estimate(Duration::from_secs(51 * 3600));
+        assert_eq!(estimate.finish, None);
+        assert!(estimate.to_string().ends_with(", by –"));
+    }
+}
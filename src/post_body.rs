@@ -1,20 +1,20 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
-pub(crate) struct Gameplay {
+pub struct Gameplay {
     pub perspective: String,
     pub flow: String,
     pub genre: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub(crate) struct Range {
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Range {
     pub min: Option<u16>,
     pub max: Option<u16>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-pub(crate) struct Games {
+pub struct Games {
     #[serde(rename = "userId")]
     pub user_id: i64,
     pub platform: String,
@@ -31,21 +31,21 @@ pub(crate) struct Games {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-pub(crate) struct SearchOptions {
+pub struct SearchOptions {
     pub games: Games,
     pub filter: String,
     pub sort: u8
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-pub(crate) struct Body {
+pub struct Body {
     #[serde(rename = "searchType")]
     pub search_type: String,
     #[serde(rename = "searchTerms")]
     pub search_terms: Vec<String>,
     #[serde(rename = "searchPage")]
     pub search_page: i64,
-    pub size: u8,
+    pub size: u32,
     #[serde(rename = "searchOptions")]
     pub search_options: SearchOptions,
     #[serde(rename = "useCache")]
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-fn deserialize_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+pub(crate) fn deserialize_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
@@ -10,7 +10,7 @@ where
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct Game {
+pub struct Game {
     pub game_id: i64,
     pub game_name: String,
     pub game_name_date: i64,
@@ -55,7 +55,7 @@ pub(crate) struct Game {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct PostResult {
+pub struct PostResult {
     pub color: String,
     pub title: String,
     pub category: String,
@@ -0,0 +1,105 @@
+//! Checks for completion times that look unreliable or wrong.
+
+use std::time::Duration;
+use schemars::JsonSchema;
+use serde::Serialize;
+use strum_macros::IntoStaticStr;
+use crate::game_page::GameDetails;
+use crate::post_result::Game;
+
+/// How many times longer than the previous category a time can be before it's flagged.
+pub const GAP_RATIO: f64 = 4.0;
+
+/// Something suspicious about the main story, main + extras and completionist times of a game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, JsonSchema, IntoStaticStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Warning {
+    /// Main + extras is shorter than the main story.
+    ExtrasShorterThanMain,
+    /// Completionist is shorter than main + extras.
+    CompletionistShorterThanExtras,
+    /// The completionist time comes from a single submission.
+    SingleCompletionistSubmission,
+    /// A category takes more than [`GAP_RATIO`] times as long as the previous one.
+    LargeGap,
+}
+
+impl Warning {
+    /// Checks the main story, main + extras and completionist times, given
+    /// with their numbers of submissions. Times nobody submitted are skipped.
+    pub fn check(main: (Duration, u32), extras: (Duration, u32), completionist: (Duration, u32)) -> Vec<Warning> {
+        let submitted = |(time, count): (Duration, u32)| (count > 0 && !time.is_zero()).then_some(time);
+        let (main_time, extras_time, completionist_time) = (submitted(main), submitted(extras), submitted(completionist));
+        let mut warnings = Vec::new();
+
+        if let (Some(main), Some(extras)) = (main_time, extras_time) {
+            if extras < main { warnings.push(Warning::ExtrasShorterThanMain) }
+        }
+        if let (Some(extras), Some(completionist)) = (extras_time, completionist_time) {
+            if completionist < extras { warnings.push(Warning::CompletionistShorterThanExtras) }
+        }
+        if completionist_time.is_some() && completionist.1 == 1 {
+            warnings.push(Warning::SingleCompletionistSubmission);
+        }
+
+        let times: Vec<Duration> = [main_time, extras_time, completionist_time].into_iter().flatten().collect();
+        if times.windows(2).any(|pair| pair[1].as_secs_f64() > pair[0].as_secs_f64() * GAP_RATIO) {
+            warnings.push(Warning::LargeGap);
+        }
+        warnings
+    }
+
+    /// The name used in machine-readable output, e.g. `extras_shorter_than_main`,
+    /// the variant in snake_case like it's serialized.
+    pub fn code(&self) -> &'static str {
+        self.into()
+    }
+}
+
+impl Game {
+    /// Suspicious things about the completion times, see [`Warning::check`].
+    pub fn warnings(&self) -> Vec<Warning> {
+        Warning::check(
+            (self.comp_main, self.comp_main_count),
+            (self.comp_plus, self.comp_plus_count),
+            (self.comp_100, self.comp_100_count),
+        )
+    }
+
+    /// Forgets every time with fewer than `min_submissions` submissions, as if nobody had submitted it.
+    pub fn drop_unreliable_times(&mut self, min_submissions: u32) {
+        let times = [
+            (&mut self.comp_main, &mut self.comp_main_count),
+            (&mut self.comp_plus, &mut self.comp_plus_count),
+            (&mut self.comp_100, &mut self.comp_100_count),
+            (&mut self.comp_all, &mut self.comp_all_count),
+            (&mut self.invested_co, &mut self.invested_co_count),
+            (&mut self.invested_mp, &mut self.invested_mp_count),
+        ];
+        for (time, count) in times {
+            if *count < min_submissions {
+                *time = Duration::ZERO;
+                *count = 0;
+            }
+        }
+    }
+
+    /// Whether anybody submitted any time for the game.
+    pub fn has_times(&self) -> bool {
+        [self.comp_main_count, self.comp_plus_count, self.comp_100_count, self.comp_all_count, self.invested_co_count, self.invested_mp_count]
+            .iter()
+            .any(|count| *count > 0)
+    }
+}
+
+impl GameDetails {
+    /// Suspicious things about the average completion times, see [`Warning::check`].
+    pub fn warnings(&self) -> Vec<Warning> {
+        Warning::check(
+            (self.comp_main, self.comp_main_count),
+            (self.comp_plus, self.comp_plus_count),
+            (self.comp_100, self.comp_100_count),
+        )
+    }
+}
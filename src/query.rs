@@ -0,0 +1,311 @@
+use strum_macros::{Display, EnumString};
+use crate::post_body::{Body, Games, Gameplay, Range, SearchOptions};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumString, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum SortCategory {
+    #[strum(serialize = "name")]
+    Name,
+    #[strum(serialize = "main")]
+    Main,
+    #[strum(serialize = "mainp")]
+    #[cfg_attr(feature = "cli", value(aliases = &["extras", "extra"]))]
+    MainExtras,
+    #[strum(serialize = "comp")]
+    #[cfg_attr(feature = "cli", value(aliases = &["comp", "completion"]))]
+    Completionist,
+    #[strum(serialize = "averagea")]
+    #[cfg_attr(feature = "cli", value(alias = "time"))]
+    AverageTime,
+    #[strum(serialize = "rating")]
+    #[cfg_attr(feature = "cli", value(alias = "rating"))]
+    TopRated,
+    #[default]
+    #[strum(serialize = "popular")]
+    #[cfg_attr(feature = "cli", value(aliases = &["popular", "popularity"]))]
+    MostPopular,
+    #[strum(serialize = "backlog")]
+    #[cfg_attr(feature = "cli", value(aliases = &["backlog", "backlogs"]))]
+    MostBacklogs,
+    #[strum(serialize = "usersp")]
+    #[cfg_attr(feature = "cli", value(aliases = &["submissions", "beat"]))]
+    MostSubmissions,
+    #[strum(serialize = "playing")]
+    #[cfg_attr(feature = "cli", value(aliases = &["playing", "played"]))]
+    MostPlayed,
+    #[strum(serialize = "speedruns")]
+    #[cfg_attr(feature = "cli", value(alias = "speedrun"))]
+    MostSpeedruns,
+    #[strum(serialize = "reviews")]
+    #[cfg_attr(feature = "cli", value(alias = "reviews"))]
+    MostReviews,
+    #[strum(serialize = "release")]
+    #[cfg_attr(feature = "cli", value(alias = "release"))]
+    ReleaseDate
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumString, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Platform {
+    #[default]
+    #[strum(serialize = "")]
+    All,
+    #[strum(serialize = "Emulated")]
+    Emulated,
+    #[strum(serialize = "Nintendo 3DS")]
+    #[cfg_attr(feature = "cli", value(name = "nintendo-3ds", aliases = &["nintendo 3ds", "nintendo3ds", "3ds"]))]
+    Nintendo3DS,
+    #[strum(serialize = "Nintendo Switch")]
+    #[cfg_attr(feature = "cli", value(aliases = &["nintendo switch", "switch"]))]
+    NintendoSwitch,
+    #[cfg_attr(feature = "cli", value(alias = "desktop"))]
+    PC,
+    #[strum(serialize = "PlayStation 3")]
+    #[cfg_attr(feature = "cli", value(name = "playstation3", aliases = &["playstation 3", "ps3", "ps 3"]))]
+    PlayStation3,
+    #[strum(serialize = "PlayStation 4")]
+    #[cfg_attr(feature = "cli", value(name = "playstation4", aliases = &["playstation 4", "ps4", "ps 4"]))]
+    PlayStation4,
+    #[strum(serialize = "PlayStation 5")]
+    #[cfg_attr(feature = "cli", value(name = "playstation5", aliases = &["playstation 5", "ps5", "ps 5"]))]
+    PlayStation5,
+    #[strum(serialize = "PlayStation Now")]
+    #[cfg_attr(feature = "cli", value(name = "playstation-now", aliases = &["playstation now", "playstationnow", "psnow", "ps now"]))]
+    PlayStationNow,
+    #[strum(serialize = "Wii U")]
+    #[cfg_attr(feature = "cli", value(aliases = &["wii u", "wiiu"]))]
+    WiiU,
+    #[strum(serialize = "Xbox 360")]
+    #[cfg_attr(feature = "cli", value(aliases = &["xbox 360", "xbox-360", "x360", "360"]))]
+    Xbox360,
+    #[strum(serialize = "Xbox One")]
+    #[cfg_attr(feature = "cli", value(aliases = &["xbox one", "xboxone", "xone", "one"]))]
+    XboxOne,
+    #[strum(serialize = "Xbox Series X/S")]
+    #[cfg_attr(feature = "cli", value(aliases = &["xbox xs", "xbox-xs", "xboxxs", "xs"]))]
+    XboxSeriesXS
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumString, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Perspective {
+    #[default]
+    #[strum(serialize = "")]
+    All,
+    #[strum(serialize = "First-Person")]
+    #[cfg_attr(feature = "cli", value(aliases = &["firstperson", "first person", "pov"]))]
+    FirstPerson,
+    Isometric,
+    Side,
+    Text,
+    #[strum(serialize = "Third-Person")]
+    #[cfg_attr(feature = "cli", value(aliases = &["thirdperson", "third person"]))]
+    ThirdPerson,
+    #[strum(serialize = "Top-Down")]
+    #[cfg_attr(feature = "cli", value(aliases = &["topdown", "top down"]))]
+    TopDown,
+    #[strum(serialize = "Virtual Reality")]
+    #[cfg_attr(feature = "cli", value(aliases = &["vr", "virtual-reality", "virtualreality"]))]
+    VirtualReality
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumString, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Flow {
+    #[default]
+    #[strum(serialize = "")]
+    All,
+    Incremental,
+    #[strum(serialize = "Massively Multiplayer")]
+    #[cfg_attr(feature = "cli", value(aliases = &["massivelymultiplayer", "massively multiplayer"]))]
+    MassivelyMultiplayer,
+    Multidirectional,
+    #[strum(serialize = "On-Rails")]
+    #[cfg_attr(feature = "cli", value(aliases = &["onrails", "on rails"]))]
+    OnRails,
+    #[strum(serialize = "Point-and-Click")]
+    #[cfg_attr(feature = "cli", value(aliases = &["pointandclick", "point and click"]))]
+    PointAndClick,
+    #[strum(serialize = "Real-Time")]
+    #[cfg_attr(feature = "cli", value(aliases = &["realtime", "real time", "rt"]))]
+    RealTime,
+    Scrolling,
+    #[strum(serialize = "Turn-Based")]
+    #[cfg_attr(feature = "cli", value(aliases = &["turnbased", "turn based", "turn"]))]
+    TurnBased
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumString, Display)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Genre {
+    #[default]
+    #[strum(serialize = "")]
+    All,
+    Action,
+    Adventure,
+    Arcade,
+    #[strum(serialize = "Battle Arena")]
+    #[cfg_attr(feature = "cli", value(aliases = &["battle-arena", "battle arena"]))]
+    BattleArena,
+    #[strum(serialize = "Beat em Up")]
+    #[cfg_attr(feature = "cli", value(aliases = &["beat-em-up", "beat em up"]))]
+    BeatEmUp,
+    #[strum(serialize = "Board Game")]
+    #[cfg_attr(feature = "cli", value(aliases = &["board-game", "board game"]))]
+    BoardGame,
+    Breakout,
+    #[strum(serialize = "Card Game")]
+    #[cfg_attr(feature = "cli", value(aliases = &["card-game", "card game"]))]
+    CardGame,
+    #[strum(serialize = "City-Building")]
+    #[cfg_attr(feature = "cli", value(aliases = &["city-building", "city building"]))]
+    CityBuilding,
+    Compilation,
+    Educational,
+    Fighting,
+    Fitness,
+    Flight,
+    #[strum(serialize = "Full Motion Video (FMV)")]
+    #[cfg_attr(feature = "cli", value(aliases = &["full-motion-video", "full motion video", "fmv"]))]
+    FullMotionVideo,
+    #[strum(serialize = "Hack and Slash")]
+    #[cfg_attr(feature = "cli", value(aliases = &["hack-and-slash", "hack and slash"]))]
+    HackAndSlash,
+    #[strum(serialize = "Hidden Object")]
+    #[cfg_attr(feature = "cli", value(aliases = &["hidden-object", "hidden object"]))]
+    HiddenObject,
+    Horror,
+    #[strum(serialize = "Interactive Art")]
+    #[cfg_attr(feature = "cli", value(aliases = &["interactive-art", "interactive art"]))]
+    InteractiveArt,
+    Management,
+    #[strum(serialize = "Music/Rhythm")]
+    #[cfg_attr(feature = "cli", value(aliases = &["music/rhythm", "music-rhythm", "music rhythm", "music", "rhythm"]))]
+    MusicRhythm,
+    #[strum(serialize = "Open World")]
+    #[cfg_attr(feature = "cli", value(aliases = &["open-world", "open world"]))]
+    OpenWorld,
+    Party,
+    Pinball,
+    Platform,
+    Puzzle,
+    #[strum(serialize = "Racing/Driving")]
+    #[cfg_attr(feature = "cli", value(aliases = &["racing/driving", "racing-driving", "racing driving", "racing", "driving"]))]
+    RacingDriving,
+    Roguelike,
+    #[strum(serialize = "Role-Playing")]
+    #[cfg_attr(feature = "cli", value(aliases = &["role-playing", "role playing", "rpg"]))]
+    RolePlaying,
+    Sandbox,
+    Shooter,
+    Simulation,
+    Social,
+    Sports,
+    Stealth,
+    #[strum(serialize = "Strategy/Tactical")]
+    #[cfg_attr(feature = "cli", value(aliases = &["strategy/tactical", "strategy-tactical", "strategy tactical", "strategy", "tactical"]))]
+    StrategyTactical,
+    Survival,
+    #[strum(serialize = "Tower Defense")]
+    #[cfg_attr(feature = "cli", value(aliases = &["tower-defense", "tower defense", "td"]))]
+    TowerDefense,
+    Trivia,
+    #[strum(serialize = "Vehicular Combat")]
+    #[cfg_attr(feature = "cli", value(aliases = &["vehicular-combat", "vehicular combat"]))]
+    VehicularCombat,
+    #[strum(serialize = "Visual Novel")]
+    #[cfg_attr(feature = "cli", value(aliases = &["visual-novel", "visual novel"]))]
+    VisualNovel
+}
+
+/// Whether DLCs are included in the results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumString, Display)]
+pub enum DlcFilter {
+    #[default]
+    #[strum(serialize = "Modifiers")]
+    Include,
+    #[strum(serialize = "only_dlc")]
+    Only,
+    #[strum(serialize = "hide_dlc")]
+    Hide
+}
+
+/// A game search, translated into the request [`Body`] expected by HowLongToBeat.
+#[derive(Clone, Debug)]
+pub struct SearchQuery {
+    pub terms: Vec<String>,
+    /// Page to fetch, starting at 1.
+    pub page: u32,
+    /// Number of games per page.
+    pub size: u32,
+    pub sort: SortCategory,
+    pub reverse: bool,
+    pub platform: Platform,
+    pub perspective: Perspective,
+    pub flow: Flow,
+    pub genre: Genre,
+    pub range_time: Range,
+    pub range_year: Range,
+    pub dlc: DlcFilter,
+}
+
+impl Default for SearchQuery {
+    fn default() -> Self {
+        SearchQuery {
+            terms: Vec::new(),
+            page: 1,
+            size: 5,
+            sort: SortCategory::default(),
+            reverse: false,
+            platform: Platform::default(),
+            perspective: Perspective::default(),
+            flow: Flow::default(),
+            genre: Genre::default(),
+            range_time: Range::default(),
+            range_year: Range::default(),
+            dlc: DlcFilter::default(),
+        }
+    }
+}
+
+impl SearchQuery {
+    /// Creates a query for the given words, splitting any of them that contain whitespace.
+    pub fn new<I, S>(terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let terms = terms.into_iter()
+            .flat_map(|term| term.as_ref().split_whitespace().map(str::to_string).collect::<Vec<_>>())
+            .collect();
+        SearchQuery { terms, ..Default::default() }
+    }
+
+    pub fn to_body(&self) -> Body {
+        Body {
+            search_type: "games".to_string(),
+            search_terms: self.terms.clone(),
+            search_page: self.page as i64,
+            size: self.size,
+            search_options: SearchOptions {
+                games: Games {
+                    user_id: 0,
+                    platform: self.platform.to_string(),
+                    sort_category: self.sort.to_string(),
+                    range_category: "main".to_string(),
+                    range_time: self.range_time.clone(),
+                    gameplay: Gameplay {
+                        perspective: self.perspective.to_string(),
+                        flow: self.flow.to_string(),
+                        genre: self.genre.to_string(),
+                    },
+                    range_year: self.range_year.clone(),
+                    modifier: self.dlc.to_string(),
+                },
+                filter: "".to_string(),
+                sort: self.reverse as u8
+            },
+            use_cache: true,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token bucket limiting how many requests are sent per second. Clones of an
+/// [`HltbClient`](crate::HltbClient) share it, so concurrent searches and
+/// image downloads are limited together.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_second: f64,
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// Allows `per_second` requests per second on average, and bursts of up to `burst` requests.
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        let burst = burst.max(1) as f64;
+        RateLimiter { per_second, burst, bucket: Mutex::new(Bucket { tokens: burst, updated: Instant::now() }) }
+    }
+
+    pub fn per_second(&self) -> f64 {
+        self.per_second
+    }
+
+    /// Waits until a request may be sent.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap();
+                let now = Instant::now();
+                let refill = now.duration_since(bucket.updated).as_secs_f64() * self.per_second;
+                bucket.tokens = (bucket.tokens + refill).min(self.burst);
+                bucket.updated = now;
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.per_second)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
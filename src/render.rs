@@ -0,0 +1,119 @@
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use howlongtobeat::SearchPage;
+use howlongtobeat::schema::{SearchResults, Versioned};
+use crate::args::{GlobalArgs, ToggleOption};
+use crate::cli_error::CliError;
+use crate::tabular::{print_delimited, Column, Delimited};
+use crate::template::Template;
+
+/// What the output can show, decided once from the options and where the output goes.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Terminal {
+    /// Colors and other text styles.
+    pub(crate) color: bool,
+    /// Clickable links, e.g. to Steam store pages.
+    pub(crate) hyperlinks: bool,
+    /// Cover images, drawn with `viuer` straight to stdout.
+    pub(crate) images: bool,
+    /// Width to fit tables to, `None` when there is no limit.
+    pub(crate) width: Option<usize>,
+}
+
+impl Terminal {
+    pub(crate) fn detect(global: &GlobalArgs) -> Self {
+        let stdout = global.output.is_none();
+        // only ask colored once it's known that output goes to stdout, as that's the stream it checks
+        let color = global.color == ToggleOption::Always && stdout && colored::control::SHOULD_COLORIZE.should_colorize();
+        let width = std::env::var("COLUMNS").ok().and_then(|columns| columns.trim().parse().ok())
+            .or_else(|| (stdout && std::io::stdout().is_terminal()).then(|| viuer::terminal_size().0 as usize));
+
+        // covers aren't cached, so there are none to show offline
+        let images = global.images && stdout && !global.offline;
+        Terminal { color, hyperlinks: color, images, width }
+    }
+}
+
+/// Where output goes, stdout or the `--output` file, along with what it can show.
+pub(crate) struct Output {
+    writer: Box<dyn Write>,
+    pub(crate) terminal: Terminal,
+}
+
+impl Output {
+    pub(crate) fn open(global: &GlobalArgs) -> Result<Self, CliError> {
+        let writer: Box<dyn Write> = match &global.output {
+            Some(path) => {
+                let file = File::create(path).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+                Box::new(BufWriter::new(file))
+            }
+            None => Box::new(io::stdout()),
+        };
+        Ok(Output { writer, terminal: Terminal::detect(global) })
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Writes a page of search results in one of the output formats or layouts.
+pub(crate) trait Renderer {
+    fn render(&self, out: &mut dyn Write, page: &SearchPage) -> Result<(), CliError>;
+}
+
+/// The normalized `--json` document.
+pub(crate) struct JsonRenderer<'a> {
+    pub(crate) base_url: &'a str,
+}
+
+impl Renderer for JsonRenderer<'_> {
+    fn render(&self, out: &mut dyn Write, page: &SearchPage) -> Result<(), CliError> {
+        let results = Versioned::new(SearchResults::from_page(page, self.base_url));
+        writeln!(out, "{}", serde_json::to_string(&results).unwrap())?;
+        Ok(())
+    }
+}
+
+/// The page exactly as HowLongToBeat sent it.
+pub(crate) struct RawRenderer;
+
+impl Renderer for RawRenderer {
+    fn render(&self, out: &mut dyn Write, page: &SearchPage) -> Result<(), CliError> {
+        writeln!(out, "{}", serde_json::to_string(page).unwrap())?;
+        Ok(())
+    }
+}
+
+/// CSV or TSV with a header row.
+pub(crate) struct DelimitedRenderer<'a> {
+    pub(crate) delimited: Delimited,
+    pub(crate) columns: &'a [Column],
+}
+
+impl Renderer for DelimitedRenderer<'_> {
+    fn render(&self, out: &mut dyn Write, page: &SearchPage) -> Result<(), CliError> {
+        print_delimited(out, self.delimited, self.columns, &page.data)?;
+        Ok(())
+    }
+}
+
+/// A `--template` line per game.
+pub(crate) struct TemplateRenderer<'a> {
+    pub(crate) template: &'a Template,
+}
+
+impl Renderer for TemplateRenderer<'_> {
+    fn render(&self, out: &mut dyn Write, page: &SearchPage) -> Result<(), CliError> {
+        for game in &page.data {
+            writeln!(out, "{}", self.template.render(game))?;
+        }
+        Ok(())
+    }
+}
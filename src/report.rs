@@ -0,0 +1,251 @@
+use std::io::{self, Write};
+use std::time::Duration;
+use futures::future::join_all;
+use howlongtobeat::{Game, GameDetails, HltbClient};
+use crate::args::OutputFormat;
+use crate::display_time_components::{get_accuracy_color, time_components};
+use crate::duration_formatter::DurationFormatter;
+use crate::locale::messages;
+
+/// What the Markdown and HTML reports show about a game.
+pub(crate) struct ReportRow {
+    id: i64,
+    name: String,
+    year: Option<String>,
+    image: String,
+    steam: u32,
+    rating: u8,
+    times: Vec<(&'static str, Duration, u32)>,
+}
+
+impl ReportRow {
+    pub(crate) fn from_game(game: &Game) -> Self {
+        ReportRow {
+            id: game.game_id,
+            name: game.game_name.clone(),
+            year: (game.release_world > 0).then(|| game.release_world.to_string()),
+            image: game.game_image.clone(),
+            steam: game.profile_steam,
+            rating: game.review_score,
+            times: time_components(game),
+        }
+    }
+
+    pub(crate) fn from_details(details: &GameDetails) -> Self {
+        let m = messages();
+        let mut times = if details.comp_lvl_combine == 1 {
+            vec![
+                (m.solo, details.comp_all, details.comp_all_count),
+                (m.coop, details.invested_co_avg, details.invested_co_count),
+                (m.versus, details.invested_mp_avg, details.invested_mp_count),
+            ]
+        } else {
+            vec![
+                (m.main_story, details.comp_main, details.comp_main_count),
+                (m.main_extra, details.comp_plus, details.comp_plus_count),
+                (m.completionist, details.comp_100, details.comp_100_count),
+            ]
+        };
+        times.retain(|(_, _, count)| *count > 0);
+
+        ReportRow {
+            id: details.game_id,
+            name: details.game_name.clone(),
+            year: details.release_world.get(..4).map(str::to_string),
+            image: details.game_image.clone(),
+            steam: details.profile_steam,
+            rating: details.review_score,
+            times,
+        }
+    }
+
+    fn rating(&self) -> String {
+        if self.rating == 0 { messages().not_rated.to_string() } else { format!("{}%", self.rating) }
+    }
+
+    fn steam_url(&self) -> Option<String> {
+        (self.steam > 0).then(|| format!("https://store.steampowered.com/app/{}", self.steam))
+    }
+}
+
+/// A titled group of rows, e.g. the results of one search of a batch.
+pub(crate) struct ReportSection {
+    pub(crate) title: Option<String>,
+    pub(crate) rows: Vec<ReportRow>,
+}
+
+/// Time columns in the order they first appear, so single-player and
+/// multiplayer games can share a table.
+fn time_columns(rows: &[ReportRow]) -> Vec<&'static str> {
+    let mut columns = Vec::new();
+    for (label, _, _) in rows.iter().flat_map(|row| &row.times) {
+        if !columns.contains(label) {
+            columns.push(*label);
+        }
+    }
+    columns
+}
+
+fn time_for<'a>(row: &'a ReportRow, column: &str) -> Option<&'a (&'static str, Duration, u32)> {
+    row.times.iter().find(|(label, _, _)| *label == column)
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|").replace('[', "\\[").replace(']', "\\]")
+}
+
+/// A GitHub-flavored Markdown table per section.
+pub(crate) fn markdown(sections: &[ReportSection], base_url: &str) -> String {
+    let m = messages();
+    let mut out = String::new();
+    for (index, section) in sections.iter().enumerate() {
+        if index > 0 { out.push('\n') }
+        if let Some(title) = &section.title {
+            out.push_str(&format!("## {}\n\n", escape_markdown(title)));
+        }
+        if section.rows.is_empty() {
+            out.push_str(&format!("{}\n", m.no_games));
+            continue;
+        }
+
+        let columns = time_columns(&section.rows);
+        let mut header = vec![m.game.to_string(), m.year.to_string()];
+        header.extend(columns.iter().map(|column| column.to_string()));
+        header.extend([m.rating.to_string(), m.steam.to_string()]);
+        let mut alignment = vec![":---", "---:"];
+        alignment.extend(columns.iter().map(|_| "---:"));
+        alignment.extend(["---:", ":---"]);
+
+        out.push_str(&format!("| {} |\n", header.join(" | ")));
+        out.push_str(&format!("| {} |\n", alignment.join(" | ")));
+        for row in &section.rows {
+            let mut cells = vec![
+                format!("[{}]({}/game/{})", escape_markdown(&row.name), base_url, row.id),
+                row.year.clone().unwrap_or_default(),
+            ];
+            cells.extend(columns.iter().map(|column| match time_for(row, column) {
+                Some((_, time, _)) => time.format(),
+                None => "–".to_string(),
+            }));
+            cells.push(row.rating());
+            cells.push(row.steam_url().map(|url| format!("[{}]({})", m.store, url)).unwrap_or_default());
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn data_uri(bytes: &[u8]) -> Option<String> {
+    let mime = match bytes {
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        _ => return None,
+    };
+    Some(format!("data:{};base64,{}", mime, base64(bytes)))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Downloads the covers of every row, leaving out the ones that can't be fetched.
+pub(crate) async fn fetch_covers(client: &HltbClient, sections: &[ReportSection]) -> Vec<Vec<Option<Vec<u8>>>> {
+    join_all(sections.iter().map(|section| {
+        join_all(section.rows.iter().map(|row| async move {
+            if row.image.is_empty() { return None }
+            client.fetch_image(&row.image).await.ok()
+        }))
+    })).await
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { padding: 0.4em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }
+td.time, th.time { text-align: right; }
+img { width: 48px; border-radius: 3px; }
+.badge { display: inline-block; padding: 0.15em 0.5em; border-radius: 0.8em; color: #fff; font-weight: 600; }
+a { color: #287fc2; }";
+
+/// A self-contained HTML page, with covers embedded as data URIs and times
+/// colored by how many people submitted them, like in the terminal.
+pub(crate) fn html(sections: &[ReportSection], covers: &[Vec<Option<Vec<u8>>>], base_url: &str, title: &str) -> String {
+    let m = messages();
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape_html(title), HTML_STYLE, escape_html(title)
+    );
+
+    for (section, covers) in sections.iter().zip(covers) {
+        if let Some(title) = &section.title {
+            out.push_str(&format!("<h2>{}</h2>\n", escape_html(title)));
+        }
+        if section.rows.is_empty() {
+            out.push_str(&format!("<p>{}</p>\n", escape_html(m.no_games)));
+            continue;
+        }
+
+        let columns = time_columns(&section.rows);
+        out.push_str(&format!("<table>\n<tr><th></th><th>{}</th><th>{}</th>", escape_html(m.game), escape_html(m.year)));
+        for column in &columns {
+            out.push_str(&format!("<th class=\"time\">{}</th>", escape_html(column)));
+        }
+        out.push_str(&format!("<th>{}</th><th>{}</th></tr>\n", escape_html(m.rating), escape_html(m.steam)));
+
+        for (row, cover) in section.rows.iter().zip(covers) {
+            let cover = cover.as_deref().and_then(data_uri)
+                .map(|uri| format!("<img src=\"{}\" alt=\"\">", uri))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "<tr><td>{}</td><td><a href=\"{}/game/{}\">{}</a></td><td>{}</td>",
+                cover, escape_html(base_url), row.id, escape_html(&row.name), row.year.as_deref().unwrap_or("")
+            ));
+            for column in &columns {
+                match time_for(row, column) {
+                    Some((_, time, count)) => {
+                        let (r, g, b) = get_accuracy_color(*count);
+                        out.push_str(&format!(
+                            "<td class=\"time\"><span class=\"badge\" style=\"background-color: rgb({}, {}, {})\" title=\"{}\">{}</span></td>",
+                            r, g, b, escape_html(&m.plural(&m.submissions, *count as u64)), time.format()
+                        ));
+                    }
+                    None => out.push_str("<td class=\"time\">–</td>"),
+                }
+            }
+            let steam = row.steam_url().map(|url| format!("<a href=\"{}\">{}</a>", url, escape_html(m.store))).unwrap_or_default();
+            out.push_str(&format!("<td>{}</td><td>{}</td></tr>\n", row.rating(), steam));
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Writes the sections as Markdown or HTML, depending on `format`.
+pub(crate) async fn print_report(out: &mut dyn Write, format: OutputFormat, client: &HltbClient, sections: &[ReportSection], title: &str) -> io::Result<()> {
+    match format {
+        OutputFormat::Html => {
+            let covers = fetch_covers(client, sections).await;
+            write!(out, "{}", html(sections, &covers, client.base_url(), title))
+        }
+        _ => write!(out, "{}", markdown(sections, client.base_url())),
+    }
+}
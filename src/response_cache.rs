@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::cache_file::{now, write_atomically, DEFAULT_TTL};
+
+/// How the [`HltbClient`](crate::HltbClient) uses its [`ResponseCache`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Answer from the cache when it has a fresh response, otherwise ask HowLongToBeat.
+    #[default]
+    Normal,
+    /// Always ask HowLongToBeat, then update the cache.
+    Refresh,
+    /// Never touch the network, answer from the cache regardless of age.
+    Offline,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedResponse {
+    key: String,
+    stored_at: u64,
+    response: String,
+}
+
+/// On-disk cache of raw search responses and game pages, keyed by the request
+/// (the origin plus the serialized search body, or the page URL).
+#[derive(Clone, Debug)]
+pub struct ResponseCache {
+    dir: Option<PathBuf>,
+    ttl: Duration,
+}
+
+impl ResponseCache {
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        ResponseCache { dir: Some(dir.into()), ttl }
+    }
+
+    /// A cache that never stores anything.
+    pub fn disabled() -> Self {
+        ResponseCache { dir: None, ttl: Duration::ZERO }
+    }
+
+    /// `$XDG_CACHE_HOME/howlongtobeat/responses` (or the platform equivalent).
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("howlongtobeat").join("responses"))
+    }
+
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        ResponseCache { ttl, ..self }
+    }
+
+    pub fn dir(&self) -> Option<&PathBuf> {
+        self.dir.as_ref()
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        Some(self.dir.as_ref()?.join(format!("{:016x}.json", fnv1a(key))))
+    }
+
+    /// Returns the response cached for `key` along with its age, regardless of the TTL.
+    pub fn load_any_age(&self, key: &str) -> Option<(String, Duration)> {
+        let contents = fs::read_to_string(self.path(key)?).ok()?;
+        let entry: CachedResponse = serde_json::from_str(&contents).ok()?;
+        // different keys can share a file name, only trust an exact match
+        if entry.key != key { return None }
+        Some((entry.response, Duration::from_secs(now().saturating_sub(entry.stored_at))))
+    }
+
+    /// Returns the response cached for `key` if it is younger than the TTL.
+    pub fn load(&self, key: &str) -> Option<String> {
+        let (response, age) = self.load_any_age(key)?;
+        if age < self.ttl { Some(response) } else { None }
+    }
+
+    /// Stores the response, ignoring failures like [`HashCache::store`](crate::HashCache::store).
+    pub fn store(&self, key: &str, response: &str) {
+        let Some(path) = self.path(key) else { return };
+        let entry = CachedResponse { key: key.to_string(), stored_at: now(), response: response.to_string() };
+        let _ = write_atomically(&path, &serde_json::to_string(&entry).unwrap());
+    }
+
+    /// Number of cached responses.
+    pub fn len(&self) -> usize {
+        let Some(dir) = &self.dir else { return 0 };
+        fs::read_dir(dir)
+            .map(|entries| entries.filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().extension().is_some_and(|extension| extension == "json"))
+                .count())
+            .unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        if let Some(dir) = &self.dir {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        match Self::default_dir() {
+            Some(dir) => ResponseCache::new(dir, DEFAULT_TTL),
+            None => ResponseCache::disabled(),
+        }
+    }
+}
+
+/// 64-bit FNV-1a, used for file names since it is stable across Rust versions.
+fn fnv1a(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
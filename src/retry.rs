@@ -0,0 +1,86 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+
+/// A request that is about to be retried, passed to [`HltbClient::on_retry`](crate::HltbClient::on_retry).
+#[derive(Clone, Debug)]
+pub struct Retry {
+    pub url: String,
+    /// Why the previous attempt failed, e.g. `429 Too Many Requests`.
+    pub reason: String,
+    /// Number of the retry, starting at 1.
+    pub attempt: u32,
+    pub delay: Duration,
+}
+
+/// How failed requests are retried: rate limiting (429), server errors (5xx),
+/// timeouts and connection failures are retried up to `max_retries` times,
+/// waiting as long as `Retry-After` asks, or a jittered exponential backoff
+/// between `base_delay` and `max_delay`. Responses asking to wait longer than
+/// `max_delay` aren't retried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retries.
+    pub fn none() -> Self {
+        RetryPolicy { max_retries: 0, ..Default::default() }
+    }
+
+    /// Delay before retry number `attempt` (starting at 0): a random duration
+    /// up to `base_delay * 2^attempt`, capped at `max_delay` ("full jitter").
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self.base_delay.saturating_mul(2u32.saturating_pow(attempt)).min(self.max_delay);
+        ceiling.mul_f64(random_fraction())
+    }
+
+    /// Delay before retry number `attempt` after `response`, or `None` if it
+    /// shouldn't be retried, including when `Retry-After` asks for more than `max_delay`.
+    pub(crate) fn delay_after_response(&self, response: &Response, attempt: u32) -> Option<Duration> {
+        let status = response.status();
+        if attempt >= self.max_retries || !(status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) {
+            return None;
+        }
+        match retry_after(response) {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// Delay before retry number `attempt` after `error`, or `None` if it shouldn't be retried.
+    pub(crate) fn delay_after_error(&self, error: &reqwest::Error, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_retries || !(error.is_timeout() || error.is_connect()) {
+            return None;
+        }
+        Some(self.backoff(attempt))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy { max_retries: 3, base_delay: Duration::from_millis(500), max_delay: Duration::from_secs(30) }
+    }
+}
+
+/// Parses `Retry-After`, given either in seconds or as an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+}
+
+/// A random number in `[0, 1)`, good enough for spreading out retries.
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
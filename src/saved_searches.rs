@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use clap::ValueEnum;
+use howlongtobeat::cache_file::write_atomically;
+use howlongtobeat::Range;
+use toml::{Table, Value};
+use crate::args::SearchArgs;
+use crate::cli_error::CliError;
+use crate::config::{command, config_error, parse_toml, scalar};
+
+/// The words a saved search looks for.
+pub(crate) const TERMS: &str = "terms";
+
+/// The options a saved search keeps besides its words: everything that
+/// changes which games are found and in what order.
+pub(crate) const QUERY_OPTIONS: [&str; 10] = ["sort", "reverse", "platform", "perspective", "flow", "genre", "range-year", "range-time", "dlc", "no-dlc"];
+
+const HEADER: &str = "# Saved searches, run them with `howlongtobeat-cli search run <name>`\n\n";
+
+/// `~/.config/howlongtobeat/searches.toml`, next to the config file.
+fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("howlongtobeat").join("searches.toml"))
+}
+
+/// `searches.toml`: a table per saved search with its `terms` and the
+/// options in [`QUERY_OPTIONS`], written like in the config file.
+pub(crate) struct SavedSearches {
+    path: Option<PathBuf>,
+    searches: BTreeMap<String, Table>,
+}
+
+impl SavedSearches {
+    /// Reads the saved searches, none when the file doesn't exist yet.
+    pub(crate) fn load() -> Result<SavedSearches, CliError> {
+        let Some(path) = default_path() else { return Ok(SavedSearches { path: None, searches: BTreeMap::new() }) };
+        let searches = match std::fs::read_to_string(&path) {
+            Ok(contents) => parse_toml(&path, &contents)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(config_error(&path, e)),
+        };
+        Ok(SavedSearches { path: Some(path), searches })
+    }
+
+    pub(crate) fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub(crate) fn error(&self, reason: impl std::fmt::Display) -> CliError {
+        match &self.path {
+            Some(path) => config_error(path, reason),
+            None => CliError::Config(reason.to_string()),
+        }
+    }
+
+    fn unknown(&self, name: &str) -> CliError {
+        if self.searches.is_empty() {
+            CliError::Usage(format!("unknown saved search {:?}, no searches are saved", name))
+        } else {
+            let known = self.searches.keys().map(String::as_str).collect::<Vec<&str>>().join(", ");
+            CliError::Usage(format!("unknown saved search {:?}, expected one of: {}", name, known))
+        }
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Result<&Table, CliError> {
+        self.searches.get(name).ok_or_else(|| self.unknown(name))
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&String, &Table)> {
+        self.searches.iter()
+    }
+
+    /// Saves `search` under `name`, replacing any search saved with that name before.
+    pub(crate) fn insert(&mut self, name: &str, search: Table) {
+        self.searches.insert(name.to_string(), search);
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) -> Result<(), CliError> {
+        self.searches.remove(name).map(|_| ()).ok_or_else(|| self.unknown(name))
+    }
+
+    /// Writes the searches back through a temporary file, creating the directory if needed.
+    pub(crate) fn write(&self) -> Result<(), CliError> {
+        let Some(path) = &self.path else {
+            return Err(CliError::Config("no configuration directory to save searches in".to_string()));
+        };
+        let contents = format!("{}{}", HEADER, toml::to_string(&self.searches).expect("tables always serialize"));
+        // an interrupted write mustn't cut off searches edited by hand
+        write_atomically(path, &contents)
+            .map_err(|e| CliError::Output(io::Error::new(e.kind(), format!("{}: {}", path.display(), e))))
+    }
+}
+
+fn choice(value: impl ValueEnum) -> Value {
+    Value::String(value.to_possible_value().map(|value| value.get_name().to_string()).unwrap_or_default())
+}
+
+/// A range the way `--range-year` and `--range-time` take it, e.g. `2015-2020`.
+fn range(range: &Range) -> Value {
+    Value::String(match (range.min, range.max) {
+        (Some(min), Some(max)) => format!("{}-{}", min, max),
+        (Some(min), None) => min.to_string(),
+        (None, _) => String::new(),
+    })
+}
+
+/// The words of a search and its options in [`QUERY_OPTIONS`], to be saved.
+pub(crate) fn to_table(args: &SearchArgs) -> Table {
+    let flags = &args.flags;
+    let values = [
+        (TERMS, Value::String(args.search.join(" "))),
+        ("sort", choice(flags.sort)),
+        ("reverse", Value::Boolean(flags.reverse)),
+        ("platform", choice(flags.platform)),
+        ("perspective", choice(flags.perspective)),
+        ("flow", choice(flags.flow)),
+        ("genre", choice(flags.genre)),
+        ("range-year", range(&flags.range_year)),
+        ("range-time", range(&flags.range_time)),
+        ("dlc", Value::Boolean(flags.dlc)),
+        ("no-dlc", Value::Boolean(flags.no_dlc)),
+    ];
+    values.into_iter().map(|(name, value)| (name.to_string(), value)).collect()
+}
+
+/// A saved search like it would be typed, with only the options that differ
+/// from their defaults, e.g. `dead --platform playstation5 --genre roguelike`.
+pub(crate) fn describe(search: &Table) -> String {
+    let command = command();
+    let mut parts = Vec::new();
+    if let Some(terms) = search.get(TERMS).and_then(|terms| scalar(terms).ok()) {
+        if !terms.trim().is_empty() {
+            parts.push(terms);
+        }
+    }
+    for name in QUERY_OPTIONS {
+        let Some(value) = search.get(name) else { continue };
+        let Some(arg) = command.get_arguments().find(|arg| arg.get_long() == Some(name)) else { continue };
+        match value {
+            Value::Boolean(true) => parts.push(format!("--{}", name)),
+            Value::Boolean(false) => {}
+            value => {
+                let Ok(value) = scalar(value) else { continue };
+                if arg.get_default_values().iter().any(|default| default.to_string_lossy().eq_ignore_ascii_case(&value)) { continue }
+                parts.push(format!("--{} {}", name, value));
+            }
+        }
+    }
+    parts.join(" ")
+}
@@ -0,0 +1,331 @@
+//! Normalized, versioned representation of search results and game pages.
+//!
+//! Unlike [`SearchPage`] and [`GameDetails`], which mirror whatever
+//! HowLongToBeat sends, these types only change together with
+//! [`SCHEMA_VERSION`]: times are hours as floats, missing values are `null`
+//! instead of zero or an empty string, and lists are arrays.
+
+use std::time::Duration;
+use schemars::schema::RootSchema;
+use schemars::{schema_for, JsonSchema};
+use serde::Serialize;
+use crate::game_page::{GameDetails, TimeCategory};
+use crate::post_result::Game;
+use crate::quality::Warning;
+use crate::SearchPage;
+
+/// Bumped whenever a field is renamed, removed or changes type. Adding fields doesn't bump it.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A top-level document, tagged with the schema version.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Versioned<T> {
+    pub schema_version: u32,
+    #[serde(flatten)]
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    pub fn new(data: T) -> Self {
+        Versioned { schema_version: SCHEMA_VERSION, data }
+    }
+}
+
+/// Output of a search.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct SearchResults {
+    /// Number of games matching the search, across all pages.
+    pub count: u32,
+    pub page: u32,
+    pub page_total: u32,
+    pub page_size: u32,
+    pub games: Vec<GameSummary>,
+}
+
+/// A game as listed in search results.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct GameSummary {
+    pub id: i64,
+    pub name: String,
+    pub alias: Option<String>,
+    /// `game`, `dlc`, `mod`...
+    pub kind: String,
+    pub image_url: Option<String>,
+    pub release_year: Option<u16>,
+    pub developer: Option<String>,
+    pub platforms: Vec<String>,
+    pub steam_app_id: Option<u32>,
+    /// Review score out of 100.
+    pub rating: Option<u8>,
+    /// Whether the times are split into solo, co-op and competitive play.
+    pub multiplayer: bool,
+    pub times: Times,
+    pub counts: Counts,
+    /// Suspicious things about the times, e.g. `extras_shorter_than_main`.
+    pub warnings: Vec<Warning>,
+}
+
+/// Average completion times, `null` when nobody has submitted one.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Times {
+    pub main: Option<Time>,
+    pub main_extras: Option<Time>,
+    pub completionist: Option<Time>,
+    pub all_styles: Option<Time>,
+    pub coop: Option<Time>,
+    pub versus: Option<Time>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Time {
+    pub hours: f64,
+    pub submissions: u32,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Counts {
+    pub completed: u32,
+    pub playing: u32,
+    pub backlog: u32,
+    pub retired: u32,
+}
+
+/// Output of the `game` command.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct GameDocument {
+    pub game: GameInfo,
+}
+
+/// Output of the `compare` command.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct CompareDocument {
+    pub games: Vec<GameInfo>,
+}
+
+/// Output of the `batch` command.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct BatchDocument {
+    pub searches: Vec<BatchSearch>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct BatchSearch {
+    pub query: String,
+    pub results: SearchResults,
+}
+
+/// A line of the `batch` NDJSON output: a game along with the search that found it.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct QueriedGame {
+    pub query: String,
+    #[serde(flatten)]
+    pub game: GameSummary,
+}
+
+/// A game with everything its game page shows.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct GameInfo {
+    pub id: i64,
+    pub name: String,
+    pub alias: Option<String>,
+    pub kind: String,
+    pub image_url: Option<String>,
+    pub summary: Option<String>,
+    pub genres: Vec<String>,
+    pub developers: Vec<String>,
+    pub publishers: Vec<String>,
+    pub platforms: Vec<String>,
+    pub steam_app_id: Option<u32>,
+    pub release_dates: ReleaseDates,
+    pub rating: Option<u8>,
+    pub multiplayer: bool,
+    pub times: DetailedTimes,
+    pub counts: Counts,
+    /// Suspicious things about the average times, e.g. `extras_shorter_than_main`.
+    pub warnings: Vec<Warning>,
+}
+
+/// Release dates as written by HowLongToBeat, usually `YYYY-MM-DD`.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct ReleaseDates {
+    pub world: Option<String>,
+    pub north_america: Option<String>,
+    pub europe: Option<String>,
+    pub japan: Option<String>,
+}
+
+/// The time table of a game page, `null` for categories nobody has submitted a time for.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DetailedTimes {
+    pub main: Option<TimeStats>,
+    pub main_extras: Option<TimeStats>,
+    pub completionist: Option<TimeStats>,
+    pub all_styles: Option<TimeStats>,
+    pub coop: Option<TimeStats>,
+    pub versus: Option<TimeStats>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct TimeStats {
+    pub submissions: u32,
+    pub average_hours: f64,
+    pub median_hours: f64,
+    pub rushed_hours: f64,
+    pub leisure_hours: f64,
+}
+
+/// The JSON Schema of the search output.
+pub fn search_schema() -> RootSchema {
+    schema_for!(Versioned<SearchResults>)
+}
+
+/// The JSON Schema of the `game` output.
+pub fn game_schema() -> RootSchema {
+    schema_for!(Versioned<GameDocument>)
+}
+
+/// The JSON Schema of the `compare` output.
+pub fn compare_schema() -> RootSchema {
+    schema_for!(Versioned<CompareDocument>)
+}
+
+/// The JSON Schema of the `batch` output.
+pub fn batch_schema() -> RootSchema {
+    schema_for!(Versioned<BatchDocument>)
+}
+
+/// The JSON Schema of a line of the search NDJSON output.
+pub fn search_line_schema() -> RootSchema {
+    schema_for!(Versioned<GameSummary>)
+}
+
+/// The JSON Schema of a line of the `game` and `compare` NDJSON output.
+pub fn game_line_schema() -> RootSchema {
+    schema_for!(Versioned<GameInfo>)
+}
+
+/// The JSON Schema of a line of the `batch` NDJSON output.
+pub fn batch_line_schema() -> RootSchema {
+    schema_for!(Versioned<QueriedGame>)
+}
+
+fn hours(time: Duration) -> f64 {
+    time.as_secs_f64() / 3600.0
+}
+
+fn text(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() { None } else { Some(value.to_string()) }
+}
+
+fn nonzero<T: Default + PartialEq>(value: T) -> Option<T> {
+    if value == T::default() { None } else { Some(value) }
+}
+
+fn list(value: &str) -> Vec<String> {
+    value.split(',').filter_map(text).collect()
+}
+
+fn image_url(base_url: &str, game_image: &str) -> Option<String> {
+    text(game_image).map(|image| format!("{}/games/{}", base_url, image))
+}
+
+fn time(average: Duration, submissions: u32) -> Option<Time> {
+    if submissions == 0 { None } else { Some(Time { hours: hours(average), submissions }) }
+}
+
+impl SearchResults {
+    /// Normalizes a page of results; `base_url` is used for image URLs.
+    pub fn from_page(page: &SearchPage, base_url: &str) -> Self {
+        SearchResults {
+            count: page.count,
+            page: page.page_current.max(0) as u32,
+            page_total: page.page_total.max(0) as u32,
+            page_size: page.page_size.max(0) as u32,
+            games: page.data.iter().map(|game| GameSummary::from_game(game, base_url)).collect(),
+        }
+    }
+}
+
+impl GameSummary {
+    pub fn from_game(game: &Game, base_url: &str) -> Self {
+        GameSummary {
+            id: game.game_id,
+            name: game.game_name.clone(),
+            alias: text(&game.game_alias),
+            kind: game.game_type.clone(),
+            image_url: image_url(base_url, &game.game_image),
+            release_year: nonzero(game.release_world),
+            developer: text(&game.profile_dev),
+            platforms: list(&game.profile_platform),
+            steam_app_id: nonzero(game.profile_steam),
+            rating: nonzero(game.review_score),
+            multiplayer: game.comp_lvl_combine == 1,
+            times: Times {
+                main: time(game.comp_main, game.comp_main_count),
+                main_extras: time(game.comp_plus, game.comp_plus_count),
+                completionist: time(game.comp_100, game.comp_100_count),
+                all_styles: time(game.comp_all, game.comp_all_count),
+                coop: time(game.invested_co, game.invested_co_count),
+                versus: time(game.invested_mp, game.invested_mp_count),
+            },
+            counts: Counts {
+                completed: game.count_comp,
+                playing: game.count_playing,
+                backlog: game.count_backlog,
+                retired: game.count_retired,
+            },
+            warnings: game.warnings(),
+        }
+    }
+}
+
+impl GameInfo {
+    pub fn from_details(details: &GameDetails, base_url: &str) -> Self {
+        let table = details.time_table();
+        let stats = |category: TimeCategory| table.iter().find(|row| row.category == category && row.count > 0).map(|row| TimeStats {
+            submissions: row.count,
+            average_hours: hours(row.average),
+            median_hours: hours(row.median),
+            rushed_hours: hours(row.rushed),
+            leisure_hours: hours(row.leisure),
+        });
+
+        GameInfo {
+            id: details.game_id,
+            name: details.game_name.clone(),
+            alias: text(&details.game_alias),
+            kind: details.game_type.clone(),
+            image_url: image_url(base_url, &details.game_image),
+            summary: text(&details.profile_summary),
+            genres: list(&details.profile_genre),
+            developers: list(&details.profile_dev),
+            publishers: list(&details.profile_pub),
+            platforms: list(&details.profile_platform),
+            steam_app_id: nonzero(details.profile_steam),
+            release_dates: ReleaseDates {
+                world: text(&details.release_world),
+                north_america: text(&details.release_na),
+                europe: text(&details.release_eu),
+                japan: text(&details.release_jp),
+            },
+            rating: nonzero(details.review_score),
+            multiplayer: details.comp_lvl_combine == 1,
+            times: DetailedTimes {
+                main: stats(TimeCategory::MainStory),
+                main_extras: stats(TimeCategory::MainExtras),
+                completionist: stats(TimeCategory::Completionist),
+                all_styles: stats(TimeCategory::AllPlayStyles),
+                coop: stats(TimeCategory::CoOp),
+                versus: stats(TimeCategory::Competitive),
+            },
+            counts: Counts {
+                completed: details.count_comp,
+                playing: details.count_playing,
+                backlog: details.count_backlog,
+                retired: details.count_retired,
+            },
+            warnings: details.warnings(),
+        }
+    }
+}
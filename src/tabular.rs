@@ -0,0 +1,252 @@
+use std::io::{self, Write};
+use std::time::Duration;
+use clap::ValueEnum;
+use howlongtobeat::{Game, GameDetails, Warning};
+use crate::duration_formatter::{chosen_time_format, DurationFormatter};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Column {
+    Name,
+    Id,
+    Alias,
+    Type,
+    Main,
+    Plus,
+    #[value(name = "100")]
+    Completionist,
+    All,
+    MainCount,
+    PlusCount,
+    #[value(name = "100-count")]
+    CompletionistCount,
+    AllCount,
+    Coop,
+    Versus,
+    Rating,
+    Platforms,
+    Year,
+    Developer,
+    SteamId,
+    Warnings,
+}
+
+pub(crate) const DEFAULT_COLUMNS: &str = "name,id,year,main,plus,100,rating";
+
+impl Column {
+    /// The number of submissions behind a time column.
+    pub(crate) fn count_column(&self) -> Option<Column> {
+        match self {
+            Column::Main => Some(Column::MainCount),
+            Column::Plus => Some(Column::PlusCount),
+            Column::Completionist => Some(Column::CompletionistCount),
+            Column::All => Some(Column::AllCount),
+            _ => None,
+        }
+    }
+
+    fn header(&self) -> String {
+        let name = self.to_possible_value().unwrap().get_name().replace('-', "_");
+        match self {
+            Column::Main | Column::Plus | Column::Completionist | Column::All | Column::Coop | Column::Versus => format!("{}_hours", name),
+            _ => name,
+        }
+    }
+}
+
+pub(crate) enum Cell {
+    Text(String),
+    Number(u64),
+    /// Written as a number of hours (or in the `--time-format`), or left empty when there is no data.
+    Hours(Duration),
+}
+
+impl Cell {
+    fn text(value: &str) -> Cell {
+        Cell::Text(value.to_string())
+    }
+
+    /// A time, left empty when nobody submitted it.
+    fn time(time: Duration, count: u32) -> Cell {
+        Cell::Hours(if count == 0 { Duration::ZERO } else { time })
+    }
+
+    fn optional(value: u64) -> Cell {
+        if value == 0 { Cell::Text(String::new()) } else { Cell::Number(value) }
+    }
+
+    pub(crate) fn render(&self) -> String {
+        match self {
+            Cell::Text(text) => text.clone(),
+            Cell::Number(number) => number.to_string(),
+            Cell::Hours(time) if time.is_zero() => String::new(),
+            // plain numbers suit spreadsheets best, unless another format was asked for
+            Cell::Hours(time) if chosen_time_format().is_some() => time.format(),
+            Cell::Hours(time) => decimal_hours(*time),
+        }
+    }
+}
+
+/// Hours with up to two decimals, like 51.5.
+pub(crate) fn decimal_hours(time: Duration) -> String {
+    let hours = format!("{:.2}", time.as_secs_f64() / 3600.0);
+    hours.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Warning codes separated by spaces, e.g. `large_gap single_completionist_submission`.
+fn warnings(warnings: &[Warning]) -> Cell {
+    Cell::Text(warnings.iter().map(Warning::code).collect::<Vec<&str>>().join(" "))
+}
+
+/// Something that can be written as a CSV/TSV row.
+pub(crate) trait Tabular {
+    fn cell(&self, column: Column) -> Cell;
+}
+
+impl Tabular for Game {
+    fn cell(&self, column: Column) -> Cell {
+        match column {
+            Column::Name => Cell::text(&self.game_name),
+            Column::Id => Cell::Number(self.game_id as u64),
+            Column::Alias => Cell::text(&self.game_alias),
+            Column::Type => Cell::text(&self.game_type),
+            Column::Main => Cell::time(self.comp_main, self.comp_main_count),
+            Column::Plus => Cell::time(self.comp_plus, self.comp_plus_count),
+            Column::Completionist => Cell::time(self.comp_100, self.comp_100_count),
+            Column::All => Cell::time(self.comp_all, self.comp_all_count),
+            Column::MainCount => Cell::Number(self.comp_main_count as u64),
+            Column::PlusCount => Cell::Number(self.comp_plus_count as u64),
+            Column::CompletionistCount => Cell::Number(self.comp_100_count as u64),
+            Column::AllCount => Cell::Number(self.comp_all_count as u64),
+            Column::Coop => Cell::time(self.invested_co, self.invested_co_count),
+            Column::Versus => Cell::time(self.invested_mp, self.invested_mp_count),
+            Column::Rating => Cell::optional(self.review_score as u64),
+            Column::Platforms => Cell::text(&self.profile_platform),
+            Column::Year => Cell::optional(self.release_world as u64),
+            Column::Developer => Cell::text(&self.profile_dev),
+            Column::SteamId => Cell::optional(self.profile_steam as u64),
+            Column::Warnings => warnings(&self.warnings()),
+        }
+    }
+}
+
+impl Tabular for GameDetails {
+    fn cell(&self, column: Column) -> Cell {
+        match column {
+            Column::Name => Cell::text(&self.game_name),
+            Column::Id => Cell::Number(self.game_id as u64),
+            Column::Alias => Cell::text(&self.game_alias),
+            Column::Type => Cell::text(&self.game_type),
+            // the averages, like the game page's time table
+            Column::Main => Cell::time(self.comp_main_avg, self.comp_main_count),
+            Column::Plus => Cell::time(self.comp_plus_avg, self.comp_plus_count),
+            Column::Completionist => Cell::time(self.comp_100_avg, self.comp_100_count),
+            Column::All => Cell::time(self.comp_all_avg, self.comp_all_count),
+            Column::MainCount => Cell::Number(self.comp_main_count as u64),
+            Column::PlusCount => Cell::Number(self.comp_plus_count as u64),
+            Column::CompletionistCount => Cell::Number(self.comp_100_count as u64),
+            Column::AllCount => Cell::Number(self.comp_all_count as u64),
+            Column::Coop => Cell::time(self.invested_co_avg, self.invested_co_count),
+            Column::Versus => Cell::time(self.invested_mp_avg, self.invested_mp_count),
+            Column::Rating => Cell::optional(self.review_score as u64),
+            Column::Platforms => Cell::text(&self.profile_platform),
+            Column::Year => Cell::text(self.release_world.get(..4).unwrap_or("")),
+            Column::Developer => Cell::text(&self.profile_dev),
+            Column::SteamId => Cell::optional(self.profile_steam as u64),
+            Column::Warnings => warnings(&self.warnings()),
+        }
+    }
+}
+
+/// Comma or tab separated values, with a header row.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Delimited {
+    Csv,
+    Tsv,
+}
+
+impl Delimited {
+    /// Quotes fields as described in RFC 4180.
+    fn escape_csv(field: &str) -> String {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+
+    /// TSV has no quoting, so tabs and line breaks are replaced with spaces.
+    fn escape_tsv(field: &str) -> String {
+        field.replace(['\t', '\n', '\r'], " ")
+    }
+
+    fn line(&self, fields: impl IntoIterator<Item = String>) -> String {
+        let (separator, escape): (&str, fn(&str) -> String) = match self {
+            Delimited::Csv => (",", Self::escape_csv),
+            Delimited::Tsv => ("\t", Self::escape_tsv),
+        };
+        fields.into_iter().map(|field| escape(&field)).collect::<Vec<String>>().join(separator)
+    }
+
+    /// The header row, with `extra` columns before the selected ones.
+    pub(crate) fn header(&self, extra: &[&str], columns: &[Column]) -> String {
+        self.line(extra.iter().map(|name| name.to_string()).chain(columns.iter().map(Column::header)))
+    }
+
+    /// A row for `record`, with `extra` fields before the selected columns.
+    pub(crate) fn row(&self, extra: &[&str], columns: &[Column], record: &impl Tabular) -> String {
+        self.line(extra.iter().map(|field| field.to_string()).chain(columns.iter().map(|column| record.cell(*column).render())))
+    }
+}
+
+/// Writes a header row and a row per record.
+pub(crate) fn print_delimited<'a, T: Tabular + 'a>(out: &mut dyn Write, delimited: Delimited, columns: &[Column], records: impl IntoIterator<Item = &'a T>) -> io::Result<()> {
+    writeln!(out, "{}", delimited.header(&[], columns))?;
+    for record in records {
+        writeln!(out, "{}", delimited.row(&[], columns, record))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_quotes_only_fields_that_need_it() {
+        assert_eq!(Delimited::escape_csv(""), "");
+        assert_eq!(Delimited::escape_csv("Pokémon Légendes: Z-A"), "Pokémon Légendes: Z-A");
+        assert_eq!(Delimited::escape_csv("Sid Meier's Civilization, VI"), "\"Sid Meier's Civilization, VI\"");
+        assert_eq!(Delimited::escape_csv("The \"Best\" Game"), "\"The \"\"Best\"\" Game\"");
+        assert_eq!(Delimited::escape_csv("two\nlines\r"), "\"two\nlines\r\"");
+    }
+
+    #[test]
+    fn tsv_replaces_separators_with_spaces() {
+        assert_eq!(Delimited::escape_tsv("a\tb\nc\r\n"), "a b c  ");
+        assert_eq!(Delimited::escape_tsv("「ゼルダ」, \"quoted\""), "「ゼルダ」, \"quoted\"");
+    }
+
+    #[test]
+    fn lines_escape_every_field() {
+        let fields = ["A, B".to_string(), "C\tD".to_string(), String::new()];
+        assert_eq!(Delimited::Csv.line(fields.clone()), "\"A, B\",C\tD,");
+        assert_eq!(Delimited::Tsv.line(fields), "A, B\tC D\t");
+    }
+
+    #[test]
+    fn decimal_hours_drop_trailing_zeros() {
+        assert_eq!(decimal_hours(Duration::ZERO), "0");
+        assert_eq!(decimal_hours(Duration::from_secs(10)), "0");
+        assert_eq!(decimal_hours(Duration::from_secs(60)), "0.02");
+        assert_eq!(decimal_hours(Duration::from_secs(10 * 3600)), "10");
+        assert_eq!(decimal_hours(Duration::from_secs(51 * 3600 + 1800)), "51.5");
+        assert_eq!(decimal_hours(Duration::from_secs(1_000_000 * 3600)), "1000000");
+    }
+
+    #[test]
+    fn times_nobody_submitted_are_empty() {
+        assert_eq!(Cell::time(Duration::from_secs(3600), 0).render(), "");
+        assert_eq!(Cell::time(Duration::from_secs(3600), 1).render(), "1");
+        assert_eq!(Cell::optional(0).render(), "");
+    }
+}
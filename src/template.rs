@@ -0,0 +1,284 @@
+use std::str::FromStr;
+use clap::ValueEnum;
+use colored::{Color, Colorize};
+use crate::cli_error::CliError;
+use crate::display_time_components::format_with_color;
+use crate::duration_formatter::DurationFormatter;
+use crate::tabular::{decimal_hours, Cell, Column, Tabular};
+
+pub(crate) const TEMPLATE_HELP: &str = "\
+Template fields are the --columns names in braces, e.g. {name} or {main}, followed by any number of filters:
+  {main:hours}  {main:minutes}  {main:duration}   time as a number of hours, minutes, or in the --time-format (the default)
+  {name:left(30)}  {rating:right(3)}  {name:truncate(20)}   padding and truncation
+  {name:upper}  {name:lower}
+  {name:color(green)}  {name:bold}  {main:accuracy}   colors, accuracy colors times by how many people submitted them
+  {rating:prefix(Rating: )}  {rating:suffix(%)}   only added when the field is not empty
+  {rating:or(NR)}   fallback when the field is empty
+Use {{ and }} for literal braces, and \\t or \\n in --template for tabs and new lines.";
+
+enum Filter {
+    Hours,
+    Minutes,
+    Duration,
+    Left(usize),
+    Right(usize),
+    Truncate(usize),
+    Upper,
+    Lower,
+    Color(Color),
+    Bold,
+    Accuracy,
+    Prefix(String),
+    Suffix(String),
+    Or(String),
+}
+
+impl Filter {
+    fn parse(filter: &str) -> Result<Filter, String> {
+        let (name, argument) = match filter.split_once('(') {
+            Some((name, rest)) => match rest.strip_suffix(')') {
+                Some(argument) => (name, Some(argument)),
+                None => return Err(format!("missing ) in {:?}", filter)),
+            },
+            None => (filter, None),
+        };
+        let width = || argument
+            .and_then(|argument| argument.trim().parse::<usize>().ok())
+            .ok_or_else(|| format!("{} needs a width, e.g. {}(10)", name, name));
+        let text = || argument
+            .map(str::to_string)
+            .ok_or_else(|| format!("{} needs an argument, e.g. {}(text)", name, name));
+
+        Ok(match name {
+            "hours" => Filter::Hours,
+            "minutes" => Filter::Minutes,
+            "duration" => Filter::Duration,
+            "left" => Filter::Left(width()?),
+            "right" => Filter::Right(width()?),
+            "truncate" => Filter::Truncate(width()?),
+            "upper" => Filter::Upper,
+            "lower" => Filter::Lower,
+            "color" => Filter::Color(Color::from_str(&text()?).map_err(|_| format!("unknown color {:?}", argument.unwrap_or("")))?),
+            "bold" => Filter::Bold,
+            "accuracy" => Filter::Accuracy,
+            "prefix" => Filter::Prefix(text()?),
+            "suffix" => Filter::Suffix(text()?),
+            "or" => Filter::Or(text()?),
+            _ => return Err(format!("unknown filter {:?}", name)),
+        })
+    }
+}
+
+enum Segment {
+    Literal(String),
+    Field(Column, Vec<Filter>),
+}
+
+/// A `--template`, parsed once and rendered for every game.
+pub(crate) struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    pub(crate) fn parse(template: &str) -> Result<Template, CliError> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => { chars.next(); literal.push('{') }
+                '}' if chars.peek() == Some(&'}') => { chars.next(); literal.push('}') }
+                '}' => return Err(CliError::Template("unmatched }, use }} for a literal brace".to_string())),
+                '{' => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => field.push(c),
+                            None => return Err(CliError::Template(format!("unclosed {{{}", field))),
+                        }
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Self::parse_field(&field).map_err(CliError::Template)?);
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Template { segments })
+    }
+
+    fn parse_field(field: &str) -> Result<Segment, String> {
+        // split on colons outside of filter arguments, so prefix(: ) works
+        let mut parts = vec![String::new()];
+        let mut depth = 0;
+        for c in field.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ':' if depth == 0 => { parts.push(String::new()); continue }
+                _ => {}
+            }
+            parts.last_mut().unwrap().push(c);
+        }
+
+        let name = parts[0].trim();
+        let column = Column::from_str(&name.replace('_', "-"), true)
+            .map_err(|_| format!("unknown field {{{}}}", name))?;
+        let filters = parts[1..].iter().map(|filter| Filter::parse(filter.trim())).collect::<Result<_, _>>()?;
+        Ok(Segment::Field(column, filters))
+    }
+
+    /// Parses a template given on the command line, where `\t` and `\n` stand for tabs and new lines.
+    pub(crate) fn parse_argument(template: &str) -> Result<Template, CliError> {
+        Self::parse(&template.replace("\\t", "\t").replace("\\n", "\n"))
+    }
+
+    pub(crate) fn render(&self, record: &impl Tabular) -> String {
+        self.segments.iter()
+            .map(|segment| match segment {
+                Segment::Literal(literal) => literal.clone(),
+                Segment::Field(column, filters) => Self::render_field(record, *column, filters),
+            })
+            .collect()
+    }
+
+    fn render_field(record: &impl Tabular, column: Column, filters: &[Filter]) -> String {
+        let cell = record.cell(column);
+        let mut value = match &cell {
+            Cell::Hours(time) if time.is_zero() => String::new(),
+            Cell::Hours(time) => time.format(),
+            cell => cell.render(),
+        };
+
+        for filter in filters {
+            value = match filter {
+                Filter::Hours => match &cell {
+                    Cell::Hours(time) if !time.is_zero() => decimal_hours(*time),
+                    _ => value,
+                },
+                Filter::Minutes => match &cell {
+                    Cell::Hours(time) if !time.is_zero() => (time.as_secs() / 60).to_string(),
+                    _ => value,
+                },
+                Filter::Duration => match &cell {
+                    Cell::Hours(time) if !time.is_zero() => time.format(),
+                    _ => value,
+                },
+                Filter::Left(width) => format!("{:<width$}", value),
+                Filter::Right(width) => format!("{:>width$}", value),
+                Filter::Truncate(width) if value.chars().count() > *width => {
+                    let mut truncated: String = value.chars().take(width.saturating_sub(1)).collect();
+                    truncated.push('…');
+                    truncated
+                }
+                Filter::Truncate(_) => value,
+                Filter::Upper => value.to_uppercase(),
+                Filter::Lower => value.to_lowercase(),
+                Filter::Color(color) => value.color(*color).to_string(),
+                Filter::Bold => value.bold().to_string(),
+                Filter::Accuracy => match column.count_column().map(|count| record.cell(count)) {
+                    Some(Cell::Number(count)) => format_with_color(&value, count as u32),
+                    _ => value,
+                },
+                Filter::Prefix(prefix) if !value.is_empty() => format!("{}{}", prefix, value),
+                Filter::Suffix(suffix) if !value.is_empty() => format!("{}{}", value, suffix),
+                Filter::Or(fallback) if value.is_empty() => fallback.clone(),
+                Filter::Prefix(_) | Filter::Suffix(_) | Filter::Or(_) => value,
+            };
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+
+    struct Row;
+
+    impl Tabular for Row {
+        fn cell(&self, column: Column) -> Cell {
+            match column {
+                Column::Name => Cell::Text("Pokémon Légendes".to_string()),
+                Column::Main => Cell::Hours(Duration::from_secs(51 * 3600 + 1800)),
+                Column::MainCount => Cell::Number(3),
+                Column::Plus => Cell::Hours(Duration::ZERO),
+                _ => Cell::Text(String::new()),
+            }
+        }
+    }
+
+    fn render(template: &str) -> String {
+        match Template::parse(template) {
+            Ok(template) => template.render(&Row),
+            Err(error) => panic!("{:?} didn't parse: {}", template, error),
+        }
+    }
+
+    fn error(template: &str) -> String {
+        match Template::parse(template) {
+            Ok(_) => panic!("{:?} parsed", template),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn literals_and_escaped_braces_are_kept() {
+        assert_eq!(render(""), "");
+        assert_eq!(render("{{name}} → {name}"), "{name} → Pokémon Légendes");
+        assert_eq!(render("}}{{"), "}{");
+    }
+
+    #[test]
+    fn times_render_in_any_unit() {
+        assert_eq!(render("{main}"), "51.5h");
+        assert_eq!(render("{main:hours}|{main:minutes}|{main:duration}"), "51.5|3090|51.5h");
+        assert_eq!(render("[{plus}{plus:hours}{plus:minutes}]"), "[]");
+    }
+
+    #[test]
+    fn width_filters_count_characters() {
+        assert_eq!(render("{name:left(18)}|"), "Pokémon Légendes  |");
+        assert_eq!(render("{name:right(17)}"), " Pokémon Légendes");
+        assert_eq!(render("{name:truncate(8)}"), "Pokémon…");
+        assert_eq!(render("{name:truncate(16)}"), "Pokémon Légendes");
+        assert_eq!(render("{name:truncate(0)}"), "…");
+        assert_eq!(render("{name:upper} {name:lower}"), "POKÉMON LÉGENDES pokémon légendes");
+    }
+
+    #[test]
+    fn empty_fields_only_take_a_fallback() {
+        assert_eq!(render("{rating:prefix(Rating: ):suffix(%)}"), "");
+        assert_eq!(render("{rating:or(NR)}"), "NR");
+        assert_eq!(render("{plus:or(–)}"), "–");
+        assert_eq!(render("{name:prefix(Game: ):or(?)}"), "Game: Pokémon Légendes");
+        assert_eq!(render("{main_count:suffix( people)}"), "3 people");
+    }
+
+    #[test]
+    fn mistakes_are_explained() {
+        assert_eq!(error("{name"), "invalid template: unclosed {name");
+        assert_eq!(error("name}"), "invalid template: unmatched }, use }} for a literal brace");
+        assert_eq!(error("{nom}"), "invalid template: unknown field {nom}");
+        assert_eq!(error("{名前}"), "invalid template: unknown field {名前}");
+        assert_eq!(error("{name:shout}"), "invalid template: unknown filter \"shout\"");
+        assert_eq!(error("{name:left}"), "invalid template: left needs a width, e.g. left(10)");
+        assert_eq!(error("{name:left(-1)}"), "invalid template: left needs a width, e.g. left(10)");
+        assert_eq!(error("{name:left(10}"), "invalid template: missing ) in \"left(10\"");
+        assert_eq!(error("{name:or}"), "invalid template: or needs an argument, e.g. or(text)");
+        assert_eq!(error("{name:color(mauve)}"), "invalid template: unknown color \"mauve\"");
+    }
+
+    #[test]
+    fn arguments_stand_for_tabs_and_new_lines() {
+        let template = Template::parse_argument("{name}\\t{main:hours}\\n").unwrap_or_else(|error| panic!("{}", error));
+        assert_eq!(template.render(&Row), "Pokémon Légendes\t51.5\n");
+    }
+}
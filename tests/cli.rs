@@ -0,0 +1,1139 @@
+#[path = "support/mock_server.rs"]
+mod mock_server;
+
+use std::fs;
+use std::process::{Command, Output};
+use mock_server::{MockOptions, MockServer, SEARCH_HASH};
+use serde_json::Value;
+use tempfile::TempDir;
+
+/// Runs the CLI against `server` with its caches and config file in `cache_dir`.
+fn run(server: &MockServer, cache_dir: &TempDir, args: &[&str]) -> Output {
+    run_with_env(server, cache_dir, &[], args)
+}
+
+/// Like [`run`], with extra environment variables.
+fn run_with_env(server: &MockServer, cache_dir: &TempDir, env: &[(&str, &str)], args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_howlongtobeat-cli"))
+        .args(args)
+        .args(["--base-url", &server.base_url, "--color", "never"])
+        .env("XDG_CACHE_HOME", cache_dir.path())
+        .env("XDG_CONFIG_HOME", cache_dir.path())
+        .env_remove("HLTB_CONFIG")
+        .env_remove("HLTB_PROFILE")
+        .env_remove("COLUMNS")
+        .env_remove("LANG")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .envs(env.iter().copied())
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
+
+/// Compares `actual` with `tests/golden/<name>`, or rewrites the file when `UPDATE_GOLDEN` is set.
+fn assert_golden(name: &str, actual: &str) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    assert_eq!(actual, expected, "output differs from {}, rerun with UPDATE_GOLDEN=1 to update it", path.display());
+}
+
+/// Writes the `config.toml` that [`run`] reads.
+fn write_config(cache_dir: &TempDir, contents: &str) {
+    let dir = cache_dir.path().join("howlongtobeat");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("config.toml"), contents).unwrap();
+}
+
+fn write_cached_hash(cache_dir: &TempDir, server: &MockServer, hash: &str) {
+    let dir = cache_dir.path().join("howlongtobeat");
+    fs::create_dir_all(&dir).unwrap();
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+    let entry = serde_json::json!({ "base_url": server.base_url, "hash": hash, "discovered_at": now });
+    fs::write(dir.join("search-hash.json"), entry.to_string()).unwrap();
+}
+
+#[test]
+fn prints_cards_for_matching_games() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["witcher"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.starts_with("Found 2 Games\n"));
+    assert!(stdout.contains("The Witcher 3: Wild Hunt"));
+    assert!(stdout.contains("The Witcher 2: Assassins of Kings"));
+    assert!(stdout.contains("Main Story:    51.5h"));
+    assert!(stdout.contains("Completionist: 173h"));
+    assert!(!stdout.contains("Developer:"));
+}
+
+#[test]
+fn info_shows_developer_and_poll_counts() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["hades", "--info"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.starts_with("Found 1 Game\n"));
+    assert!(stdout.contains("Developer: Supergiant Games"));
+    assert!(stdout.contains("Rating: 94%"));
+    assert!(stdout.contains("22.5h (1,200)"));
+}
+
+#[test]
+fn multiplayer_games_show_solo_coop_and_versus() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["overcooked"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("Solo:  12h"));
+    assert!(stdout.contains("Co-op: 13.5h"));
+    assert!(stdout.contains("Vs.  : 6h"));
+}
+
+#[test]
+fn json_outputs_the_normalized_search_result() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["witcher", "--json"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["schema_version"], 1);
+    assert_eq!(result["count"], 2);
+    let game = &result["games"][0];
+    assert_eq!(game["name"], "The Witcher 3: Wild Hunt");
+    assert_eq!(game["alias"], Value::Null);
+    assert_eq!(game["release_year"], 2015);
+    assert_eq!(game["platforms"][1], "PC");
+    assert_eq!(game["times"]["main"]["hours"], 51.5);
+    assert_eq!(game["times"]["main"]["submissions"], 2840);
+    assert_eq!(game["times"]["coop"], Value::Null);
+    assert_eq!(game["multiplayer"], false);
+    assert_eq!(game["image_url"], format!("{}/games/10270_The_Witcher_3_Wild_Hunt.jpg", server.base_url));
+}
+
+#[test]
+fn raw_outputs_the_search_result_as_received() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["witcher", "--raw"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["count"], 2);
+    assert_eq!(result["data"][0]["game_name"], "The Witcher 3: Wild Hunt");
+}
+
+#[test]
+fn json_schema_describes_the_output() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["--json-schema"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let schema: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(schema["required"].as_array().unwrap().contains(&Value::from("schema_version")));
+    assert!(schema["definitions"]["GameSummary"]["properties"]["times"].is_object());
+
+    let output = run(&server, &cache, &["game", "--json-schema"]);
+    let schema: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(schema["definitions"]["GameInfo"]["properties"]["publishers"].is_object());
+
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn images_are_fetched_and_displayed() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["hades", "--images"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Hades"));
+    assert!(server.requests().iter().any(|request| request.starts_with("GET /games/62941_Hades.jpg")));
+}
+
+#[test]
+fn dlc_filters_are_sent_to_the_server() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["--dlc", "--json"]);
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["count"], 1);
+    assert_eq!(result["games"][0]["name"], "Blood and Wine");
+    assert_eq!(result["games"][0]["kind"], "dlc");
+
+    let output = run(&server, &cache, &["--no-dlc", "--json"]);
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["count"], 4);
+}
+
+#[test]
+fn no_results_only_fail_when_requested() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["nonexistent"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Found 0 Games"));
+
+    let output = run(&server, &cache, &["nonexistent", "--fail-if-empty"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert!(stderr(&output).contains("no games found"));
+}
+
+#[test]
+fn search_hash_is_cached_between_runs() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    assert!(run(&server, &cache, &["hades"]).status.success());
+    assert!(run(&server, &cache, &["hades"]).status.success());
+
+    let homepage_requests = server.requests().iter().filter(|request| *request == "GET /").count();
+    assert_eq!(homepage_requests, 1);
+}
+
+#[test]
+fn stale_search_hash_is_rediscovered() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    write_cached_hash(&cache, &server, "0000000000000000");
+
+    let output = run(&server, &cache, &["hades", "--verbose"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains(&format!("Discovered search hash {}", SEARCH_HASH)));
+    assert!(stderr(&output).contains("strategy: concat"));
+}
+
+#[test]
+fn unexpected_search_responses_rediscover_the_hash_once() {
+    // the cached hash answers once with something that isn't a page of results
+    let server = MockServer::start_with(MockOptions { search_status: Some(200), search_failures: 1, ..Default::default() });
+    let cache = TempDir::new().unwrap();
+    write_cached_hash(&cache, &server, SEARCH_HASH);
+
+    let output = run(&server, &cache, &["hades", "--verbose"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains(&format!("Discovered search hash {}", SEARCH_HASH)));
+    let homepage_requests = server.requests().iter().filter(|request| *request == "GET /").count();
+    assert_eq!(homepage_requests, 1);
+}
+
+#[test]
+fn hashes_without_search_results_are_not_cached() {
+    let server = MockServer::start_with(MockOptions { search_status: Some(200), ..Default::default() });
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["hades"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(stderr(&output).contains("no strategy found a working hash"), "{}", stderr(&output));
+    assert!(!cache.path().join("howlongtobeat").join("search-hash.json").exists());
+
+    // nothing was cached, so the next run looks for the hash again
+    let output = run(&server, &cache, &["hades"]);
+    assert_eq!(output.status.code(), Some(4));
+    let homepage_requests = server.requests().iter().filter(|request| *request == "GET /").count();
+    assert_eq!(homepage_requests, 2);
+}
+
+#[test]
+fn search_hash_is_discovered_in_fetch_urls() {
+    let app_script = format!(r#"async function s(e){{return fetch("/api/search/{}",e)}}"#, SEARCH_HASH);
+    let server = MockServer::start_with(MockOptions { app_script, ..Default::default() });
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["hades", "--verbose"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("strategy: fetch-url"));
+}
+
+#[test]
+fn search_hash_is_discovered_in_key_value_pairs() {
+    let app_script = format!(r#"const t={{key:"{}",value:"{}"}};"#, &SEARCH_HASH[..8], &SEARCH_HASH[8..]);
+    let server = MockServer::start_with(MockOptions { app_script, ..Default::default() });
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["hades", "--verbose"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("strategy: key-value"));
+}
+
+#[test]
+fn wrong_candidate_hashes_are_rejected() {
+    let app_script = format!(
+        r#"a("/api/search/".concat("0000000000000000"));b("/api/search/{}")"#,
+        SEARCH_HASH
+    );
+    let server = MockServer::start_with(MockOptions { app_script, ..Default::default() });
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["hades", "--verbose"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains(&format!("Discovered search hash {}", SEARCH_HASH)));
+    assert!(stderr(&output).contains("strategy: fetch-url"));
+    assert!(server.requests().contains(&"POST /api/search/0000000000000000".to_string()));
+}
+
+#[test]
+fn missing_search_hash_exits_with_discovery_error() {
+    let server = MockServer::start_with(MockOptions { app_script: "console.log(1)".to_string(), ..Default::default() });
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["hades"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(stderr(&output).contains("could not discover the search endpoint"));
+}
+
+#[test]
+fn server_errors_exit_with_status_error() {
+    let server = MockServer::start_with(MockOptions { search_status: Some(500), retry_after: Some("0".to_string()), ..Default::default() });
+    let cache = TempDir::new().unwrap();
+    write_cached_hash(&cache, &server, SEARCH_HASH);
+
+    let output = run(&server, &cache, &["hades"]);
+    assert_eq!(output.status.code(), Some(5));
+    assert!(stderr(&output).contains("500 Internal Server Error"));
+    // the first attempt and 3 retries
+    assert_eq!(server.requests().len(), 4);
+
+    let output = run(&server, &cache, &["hades", "--retries", "0"]);
+    assert_eq!(output.status.code(), Some(5));
+    assert_eq!(server.requests().len(), 5);
+}
+
+#[test]
+fn long_retry_after_gives_up_instead_of_waiting() {
+    let server = MockServer::start_with(MockOptions { search_status: Some(429), retry_after: Some("86400".to_string()), ..Default::default() });
+    let cache = TempDir::new().unwrap();
+    write_cached_hash(&cache, &server, SEARCH_HASH);
+
+    let output = run(&server, &cache, &["hades"]);
+    assert_eq!(output.status.code(), Some(5));
+    assert!(stderr(&output).contains("429 Too Many Requests"));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn rate_limited_searches_are_retried() {
+    let server = MockServer::start_with(MockOptions {
+        search_status: Some(429),
+        search_failures: 2,
+        retry_after: Some("0".to_string()),
+        ..Default::default()
+    });
+    let cache = TempDir::new().unwrap();
+    write_cached_hash(&cache, &server, SEARCH_HASH);
+
+    let output = run(&server, &cache, &["hades", "--verbose"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Hades"));
+    assert!(stderr(&output).contains("after 429 Too Many Requests (attempt 2)"));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn requests_are_rate_limited() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    write_cached_hash(&cache, &server, SEARCH_HASH);
+
+    let start = std::time::Instant::now();
+    let output = run(&server, &cache, &["--size", "1", "--all", "--rate-limit", "2"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    // 5 pages: a burst of 2, then one every half second
+    assert!(start.elapsed() >= std::time::Duration::from_millis(1400));
+}
+
+#[test]
+fn unreachable_server_exits_with_network_error() {
+    let cache = TempDir::new().unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_howlongtobeat-cli"))
+        .args(["hades", "--base-url", "http://127.0.0.1:9"])
+        .env("XDG_CACHE_HOME", cache.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn page_header_shows_the_displayed_range() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["--size", "2", "--page", "2"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("Found 5 Games, showing 3–4\n"));
+}
+
+#[test]
+fn far_pages_dont_overflow_the_displayed_range() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["--size", "100000", "--page", "100000"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+}
+
+#[test]
+fn all_merges_pages_up_to_the_limit() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["--size", "2", "--all", "--json"]);
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["games"].as_array().unwrap().len(), 5);
+
+    let output = run(&server, &cache, &["--size", "2", "--all", "--limit", "3"]);
+    assert!(stdout(&output).starts_with("Found 5 Games, showing 1–3\n"));
+
+    let searches = server.requests().iter().filter(|request| request.starts_with("POST /api/search/")).count();
+    // probe + 3 pages for the first run, the second is answered from the response cache
+    assert_eq!(searches, 4);
+}
+
+#[test]
+fn game_shows_details_by_id() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["game", "10270"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.starts_with("The Witcher 3: Wild Hunt"));
+    assert!(stdout.contains("Publisher: CD Projekt, Warner Bros. Interactive Entertainment"));
+    assert!(stdout.contains("Genres: Third-Person, Action, Open World, Role-Playing"));
+    assert!(stdout.contains("Released: NA 2015-05-19, EU 2015-05-19, JP 2015-05-21, World 2015-05-19"));
+    assert!(stdout.contains("you take on the greatest contract of your life"));
+    assert!(stdout.contains("Main Story       2,840    51.5h     48h     37h      80h"));
+    assert!(!stdout.contains("Co-Op"));
+}
+
+#[test]
+fn game_resolves_names_through_search() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["game", "overcooked", "2", "--json"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let details: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let game = &details["game"];
+    assert_eq!(game["id"], 57465);
+    assert_eq!(game["multiplayer"], true);
+    assert_eq!(game["times"]["coop"]["submissions"], 81);
+    assert_eq!(game["times"]["coop"]["average_hours"], 13.5);
+    assert_eq!(game["times"]["versus"]["submissions"], 7);
+    assert_eq!(game["times"]["all_styles"]["submissions"], 65);
+    assert_eq!(game["release_dates"]["north_america"], Value::Null);
+    assert_eq!(game["publishers"], serde_json::json!(["Team17"]));
+}
+
+#[test]
+fn game_shows_cover_image() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["game", "57465", "--images"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Competitive"));
+    assert!(server.requests().iter().any(|request| request.starts_with("GET /games/57465_Overcooked_2.jpg")));
+}
+
+#[test]
+fn unknown_game_exits_with_status_error() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["game", "1"]);
+
+    assert_eq!(output.status.code(), Some(5));
+}
+
+#[test]
+fn search_subcommand_matches_the_default() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let explicit = run(&server, &cache, &["search", "witcher"]);
+    let implicit = run(&server, &cache, &["witcher"]);
+
+    assert!(explicit.status.success(), "{}", stderr(&explicit));
+    assert_eq!(stdout(&explicit), stdout(&implicit));
+}
+
+#[test]
+fn compare_lists_games_side_by_side() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["compare", "10270", "overcooked 2"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.lines().next().unwrap().contains("Main Story  Main + Extras  Completionist  Rating"));
+    assert!(stdout.contains("The Witcher 3: Wild Hunt"));
+    assert!(stdout.contains("Overcooked! 2                     9h            14h          26.5h     84%"));
+}
+
+#[test]
+fn compare_json_outputs_every_game() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["compare", "10270", "57465", "--json"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let games: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(games["schema_version"], 1);
+    assert_eq!(games["games"][0]["id"], 10270);
+    assert_eq!(games["games"][1]["id"], 57465);
+}
+
+#[test]
+fn batch_runs_a_search_per_line() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let file = cache.path().join("games.txt");
+    fs::write(&file, "hades\n\n# comment\nwitcher 3\n").unwrap();
+
+    let output = run(&server, &cache, &["batch", file.to_str().unwrap(), "--json"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let results: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let searches = results["searches"].as_array().unwrap();
+    assert_eq!(searches.len(), 2);
+    assert_eq!(searches[0]["query"], "hades");
+    assert_eq!(searches[1]["results"]["games"][0]["name"], "The Witcher 3: Wild Hunt");
+
+    let output = run(&server, &cache, &["batch", file.to_str().unwrap()]);
+    assert!(stdout(&output).starts_with("# hades\nFound 1 Game\n"));
+}
+
+#[test]
+fn batch_reports_a_missing_file() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["batch", "/nonexistent/games.txt"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("could not read input"));
+}
+
+#[test]
+fn cache_show_and_clear() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["cache", "show"]);
+    assert!(stdout(&output).contains("empty"));
+
+    write_cached_hash(&cache, &server, SEARCH_HASH);
+    let output = run(&server, &cache, &["cache", "show"]);
+    assert!(stdout(&output).contains(&format!("{} for {}", SEARCH_HASH, server.base_url)));
+
+    assert!(run(&server, &cache, &["cache", "clear"]).status.success());
+    assert!(!cache.path().join("howlongtobeat/search-hash.json").exists());
+}
+
+#[test]
+fn responses_are_cached_until_refreshed() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let searches = || server.requests().iter().filter(|request| request.starts_with("POST /api/search/")).count();
+
+    assert!(run(&server, &cache, &["hades"]).status.success());
+    assert_eq!(searches(), 2);
+    assert!(run(&server, &cache, &["hades"]).status.success());
+    assert_eq!(searches(), 2);
+    assert!(run(&server, &cache, &["hades", "--refresh"]).status.success());
+    assert_eq!(searches(), 3);
+    assert!(run(&server, &cache, &["hades", "--cache-ttl", "0"]).status.success());
+    assert_eq!(searches(), 4);
+}
+
+#[test]
+fn offline_answers_only_from_the_cache() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    assert!(run(&server, &cache, &["hades"]).status.success());
+    assert!(run(&server, &cache, &["game", "10270"]).status.success());
+    let requests = server.requests().len();
+
+    let output = run(&server, &cache, &["hades", "--offline"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Hades"));
+
+    // without the covers, which aren't cached
+    let output = run(&server, &cache, &["hades", "--offline", "--images"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = run(&server, &cache, &["game", "10270", "--offline"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("The Witcher 3: Wild Hunt"));
+
+    let output = run(&server, &cache, &["witcher", "--offline"]);
+    assert_eq!(output.status.code(), Some(9));
+    assert!(stderr(&output).contains("this search is not cached"));
+
+    assert_eq!(server.requests().len(), requests);
+}
+
+#[test]
+fn global_options_may_precede_the_command() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["--json", "game", "10270"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let details: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(details["game"]["id"], 10270);
+}
+
+#[test]
+fn search_options_may_not_precede_a_command() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["--size", "1", "search", "witcher"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("the argument '--size <SIZE>' can't be used before the search command"));
+
+    let output = run(&server, &cache, &["--size", "1", "game", "10270"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn csv_outputs_selected_columns_with_numeric_hours() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["witcher", "--format", "csv"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\
+name,id,year,main_hours,plus_hours,100_hours,rating
+The Witcher 3: Wild Hunt,10270,2015,51.5,103.25,173,93
+The Witcher 2: Assassins of Kings,10269,2011,23.75,35.5,54,86
+");
+
+    let output = run(&server, &cache, &["witcher 3", "--format", "csv", "--columns", "name,platforms,main-count,steam-id"]);
+    assert_eq!(stdout(&output), "\
+name,platforms,main_count,steam_id
+The Witcher 3: Wild Hunt,\"Nintendo Switch, PC, PlayStation 4, PlayStation 5, Xbox One, Xbox Series X/S\",2840,292030
+");
+}
+
+#[test]
+fn tsv_leaves_missing_times_empty() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["blood", "--format", "tsv", "--columns", "name,type,main,rating"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "name\ttype\tmain_hours\trating\nBlood and Wine\tdlc\t\t\n");
+}
+
+#[test]
+fn times_nobody_submitted_are_left_empty() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    // a plus time without submissions, like the table's –
+    let output = run(&server, &cache, &["quiet", "--format", "csv", "--columns", "name,main,plus,plus-count"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "name,main_hours,plus_hours,plus_count\nQuiet Prototype,10,,0\n");
+}
+
+#[test]
+fn batch_csv_prefixes_rows_with_the_query() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let file = cache.path().join("games.txt");
+    fs::write(&file, "hades\nwitcher 2\n").unwrap();
+
+    let output = run(&server, &cache, &["batch", file.to_str().unwrap(), "--format", "csv", "--columns", "id"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "query,id\nhades,62941\nwitcher 2,10269\n");
+}
+
+#[test]
+fn template_renders_each_game() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["--template", r"{name:truncate(12):left(13)}|{main:hours}\t{plus}\t{100:minutes}|{rating:suffix(%):or(NR)}"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "\
+The Witcher… |51.5\t103.5h\t10380|93%
+The Witcher… |23.75\t35.5h\t3240|86%
+Hades        |22.5\t49h\t5730|94%
+Overcooked!… |9\t14h\t1590|84%
+Blood and W… |\t\t|NR
+");
+}
+
+#[test]
+fn template_file_is_used_for_game_details() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let file = cache.path().join("template.txt");
+    fs::write(&file, "{{{name:upper}}} {coop:prefix(co-op )}{versus:prefix(, vs. )}\n").unwrap();
+
+    let output = run(&server, &cache, &["game", "57465", "--template-file", file.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "{OVERCOOKED! 2} co-op 13.5h, vs. 6h\n");
+}
+
+#[test]
+fn invalid_templates_are_rejected_before_searching() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["--template", "{nmae}"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("unknown field {nmae}"));
+
+    let output = run(&server, &cache, &["--template", "{name:left}"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("left needs a width"));
+
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn ndjson_writes_a_game_per_line_across_pages() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["--size", "2", "--all", "--limit", "3", "--format", "ndjson"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let lines: Vec<Value> = stdout(&output).lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["schema_version"], 1);
+    assert_eq!(lines[0]["name"], "The Witcher 3: Wild Hunt");
+    assert_eq!(lines[2]["name"], "Hades");
+
+    let searches = server.requests().iter().filter(|request| request.starts_with("POST /api/search/")).count();
+    // probe + the 2 pages needed for 3 games
+    assert_eq!(searches, 3);
+}
+
+#[test]
+fn ndjson_batch_lines_carry_the_query() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let file = cache.path().join("games.txt");
+    fs::write(&file, "hades\nwitcher\n").unwrap();
+
+    let output = run(&server, &cache, &["batch", file.to_str().unwrap(), "--format", "ndjson"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let lines: Vec<Value> = stdout(&output).lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["query"], "hades");
+    assert_eq!(lines[2]["query"], "witcher");
+    assert_eq!(lines[2]["id"], 10269);
+}
+
+#[test]
+fn markdown_outputs_a_linked_table() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["--format", "markdown", "--size", "4"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "| Game | Year | Main Story | Main + Extra | Completionist | Solo | Co-op | Vs. | Rating | Steam |");
+    assert_eq!(lines[2], format!(
+        "| [The Witcher 3: Wild Hunt]({}/game/10270) | 2015 | 51.5h | 103.5h | 173h | – | – | – | 93% | [Store](https://store.steampowered.com/app/292030) |",
+        server.base_url
+    ));
+    assert_eq!(lines[5], format!("| [Overcooked! 2]({}/game/57465) | 2018 | – | – | – | 12h | 13.5h | 6h | 84% | [Store](https://store.steampowered.com/app/728880) |", server.base_url));
+}
+
+#[test]
+fn html_embeds_covers_and_accuracy_colors() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["hades", "--format", "html"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.starts_with("<!DOCTYPE html>"));
+    assert!(stdout.contains("<title>HowLongToBeat: hades</title>"));
+    assert!(stdout.contains("<img src=\"data:image/png;base64,iVBORw0KGgo"));
+    assert!(stdout.contains("style=\"background-color: rgb(40, 127, 194)\" title=\"1,200 submissions\">22.5h</span>"));
+}
+
+#[test]
+fn batch_markdown_has_a_section_per_query() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let file = cache.path().join("games.txt");
+    fs::write(&file, "hades\nnonexistent\n").unwrap();
+
+    let output = run(&server, &cache, &["batch", file.to_str().unwrap(), "--format", "markdown"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.starts_with("## hades\n\n| Game |"));
+    assert!(stdout.ends_with("\n## nonexistent\n\nNo games found.\n"));
+}
+
+#[test]
+fn table_layout_aligns_a_row_per_game() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["witcher", "--layout", "table"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "Found 2 Games");
+    assert_eq!(lines[2], "Name                                Main  Main + Extra  Completionist   Beat  Rating  Year");
+    assert_eq!(lines[3], "The Witcher 3: Wild Hunt           51.5h        103.5h           173h  9,377     93%  2015");
+    assert_eq!(lines[4], "The Witcher 2: Assassins of Kings    24h         35.5h            54h  2,360     86%  2011");
+}
+
+#[test]
+fn table_layout_truncates_names_to_the_terminal_width() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run_with_env(&server, &cache, &[("COLUMNS", "80")], &["witcher", "--layout", "table"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("The Witcher 3: Wild Hu…  51.5h"));
+    assert!(stdout.contains("The Witcher 2: Assassi…    24h"));
+    assert!(stdout.lines().all(|line| line.chars().count() <= 80));
+}
+
+#[test]
+fn compact_layout_prints_a_line_per_game() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let output = run(&server, &cache, &["witcher", "--layout", "compact"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "The Witcher 3: Wild Hunt — 51.5h / 103.5h / 173h\nThe Witcher 2: Assassins of Kings — 24h / 35.5h / 54h\n");
+
+    let output = run(&server, &cache, &["overcooked", "--layout", "compact"]);
+    assert_eq!(stdout(&output), "Overcooked! 2 — Solo 12h / Co-op 13.5h / Vs. 6h\n");
+}
+
+#[test]
+fn output_is_written_to_a_file() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let file = cache.path().join("witcher.txt");
+    let output = run(&server, &cache, &["witcher", "--output", file.to_str().unwrap()]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(output.stdout.is_empty());
+    assert!(fs::read_to_string(&file).unwrap().starts_with("Found 2 Games\n"));
+
+    let output = run(&server, &cache, &["witcher", "--output", cache.path().join("missing/witcher.txt").to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(10));
+}
+
+#[test]
+fn layouts_match_golden_files() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let cases: [(&str, &[&str]); 6] = [
+        ("cards.txt", &["witcher", "--info"]),
+        ("multiplayer-cards.txt", &["overcooked"]),
+        ("table.txt", &["witcher", "--layout", "table"]),
+        ("compact.txt", &["blood", "--layout", "compact"]),
+        ("game.txt", &["game", "10270"]),
+        ("compare.txt", &["compare", "10270", "57465"]),
+    ];
+
+    for (golden, args) in cases {
+        let file = cache.path().join(golden);
+        let output = run(&server, &cache, &[args, &["--output", file.to_str().unwrap()]].concat());
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert_golden(golden, &fs::read_to_string(&file).unwrap());
+    }
+}
+
+#[test]
+fn time_format_applies_to_every_text_output() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["witcher", "--layout", "compact", "--time-format", "hm"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("The Witcher 3: Wild Hunt — 51h 30m / 103h 15m / 173h\n"));
+
+    let output = run(&server, &cache, &["hades", "--time-format", "hltb"]);
+    assert!(stdout(&output).contains("Main Story:    22½ Hours"));
+
+    let output = run(&server, &cache, &["hades", "--time-format", "hltb", "--lang", "de"]);
+    assert!(stdout(&output).contains("22½ Stunden"), "{}", stdout(&output));
+
+    let output = run(&server, &cache, &["game", "10270", "--time-format", "clock"]);
+    assert!(stdout(&output).contains("Main Story       2,840    51:30"));
+
+    let output = run(&server, &cache, &["witcher", "--time-format", "decimal", "--time-precision", "2", "--template", "{main} {main:hours}"]);
+    assert!(stdout(&output).starts_with("51.50h 51.5\n"));
+}
+
+#[test]
+fn csv_uses_numeric_hours_unless_a_time_format_is_given() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["witcher", "--format", "csv", "--columns", "name,main"]);
+    assert!(stdout(&output).contains("The Witcher 3: Wild Hunt,51.5\n"));
+
+    let output = run(&server, &cache, &["witcher", "--format", "csv", "--columns", "name,main", "--time-format", "iso"]);
+    assert!(stdout(&output).contains("The Witcher 3: Wild Hunt,PT51H30M\n"));
+}
+
+#[test]
+fn pace_estimates_days_and_finish_dates() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let in_days = |days| (chrono::Local::now().date_naive() + chrono::Days::new(days)).format("%Y-%m-%d").to_string();
+
+    let output = run(&server, &cache, &["witcher", "--pace", "2h/day"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let cards = stdout(&output);
+    assert!(cards.contains(&format!("Main Story:    51.5h   26 days, by {}", in_days(25))));
+    assert!(cards.contains(&format!("Completionist: 173h    87 days, by {}", in_days(86))));
+
+    let output = run(&server, &cache, &["witcher", "--pace", "10h/week", "--layout", "compact"]);
+    assert!(stdout(&output).starts_with("The Witcher 3: Wild Hunt — 51.5h / 103.5h / 173h · 6 / 11 / 18 weeks\n"));
+
+    let output = run(&server, &cache, &["witcher", "--pace", "2h/day", "--layout", "table"]);
+    let table = stdout(&output);
+    assert!(table.contains("Main Story by  Main + Extra by  Completionist by"), "{}", table);
+    assert!(table.contains(&format!("26 / 52 / 87 days     {}       {}        {}", in_days(25), in_days(51), in_days(86))), "{}", table);
+
+    let output = run(&server, &cache, &["witcher", "--pace", "2h/month"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn suspicious_times_are_flagged() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["tiny"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let cards = stdout(&output);
+    assert!(cards.contains("⚠ Main + Extra is shorter than Main Story\n"));
+    assert!(cards.contains("⚠ Completionist time comes from a single submission\n"));
+    assert!(cards.contains("⚠ Unusually large gap between categories\n"));
+
+    let output = run(&server, &cache, &["tiny", "--json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["games"][0]["warnings"], serde_json::json!(["extras_shorter_than_main", "single_completionist_submission", "large_gap"]));
+
+    let output = run(&server, &cache, &["tiny", "--format", "csv", "--columns", "name,warnings"]);
+    assert_eq!(stdout(&output), "name,warnings\nTiny Roguelite,extras_shorter_than_main single_completionist_submission large_gap\n");
+
+    let output = run(&server, &cache, &["witcher 3", "--json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["games"][0]["warnings"], serde_json::json!([]));
+}
+
+#[test]
+fn min_submissions_drops_unreliable_times_and_games() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["tiny", "--min-submissions", "3", "--layout", "compact"]);
+    assert_eq!(stdout(&output), "Tiny Roguelite — 20h / – / –\n");
+
+    let output = run(&server, &cache, &["blood", "--dlc", "--min-submissions", "0", "--layout", "compact"]);
+    assert!(stdout(&output).starts_with("Blood and Wine"), "{}", stdout(&output));
+
+    let output = run(&server, &cache, &["tiny", "--min-submissions", "10", "--fail-if-empty"]);
+    assert_eq!(output.status.code(), Some(8));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn lang_translates_labels_numbers_and_dates() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    let finish = (chrono::Local::now().date_naive() + chrono::Days::new(25)).format("%d.%m.%Y").to_string();
+
+    let output = run(&server, &cache, &["witcher", "--lang", "de", "--info", "--pace", "2h/day"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let cards = stdout(&output);
+    assert!(cards.starts_with("2 Spiele gefunden\n"));
+    assert!(cards.contains("Spielen gerade: 284, Backlogs: 11.360, Abgebrochen: 0,7%, Bewertung: 93%, Durchgespielt: 9.377\n"));
+    assert!(cards.contains(&format!("Hauptgeschichte: 51,5h  (2.840)  26 Tage, bis {}\n", finish)));
+
+    let output = run(&server, &cache, &["game", "10270", "--lang", "fr"]);
+    assert!(stdout(&output).contains("Histoire           2\u{202f}840    51,5h"));
+}
+
+#[test]
+fn lang_defaults_to_the_locale() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run_with_env(&server, &cache, &[("LANG", "pl_PL.UTF-8")], &["witcher", "--size", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("Znaleziono 2 gry, wyświetlono 1–1\n"));
+
+    let output = run_with_env(&server, &cache, &[("LANG", "pl_PL.UTF-8")], &["", "--size", "1"]);
+    assert!(stdout(&output).starts_with("Znaleziono 5 gier, wyświetlono 1–1\n"));
+
+    let output = run_with_env(&server, &cache, &[("LANG", "pl_PL.UTF-8"), ("LC_ALL", "es_ES.UTF-8")], &["tiny"]);
+    assert!(stdout(&output).starts_with("1 juego encontrado\n"));
+
+    let output = run_with_env(&server, &cache, &[("LANG", "de_DE.UTF-8")], &["tiny", "--lang", "en"]);
+    assert!(stdout(&output).starts_with("Found 1 Game\n"));
+
+    let output = run_with_env(&server, &cache, &[("LANG", "ja_JP.UTF-8")], &["tiny"]);
+    assert!(stdout(&output).starts_with("Found 1 Game\n"));
+}
+
+#[test]
+fn config_file_supplies_defaults_and_profiles() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    write_config(&cache, "layout = \"compact\"\nsize = 1\nlimit = 2\n\n[profiles.dlc]\ndlc = true\n");
+
+    let output = run(&server, &cache, &["witcher"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "The Witcher 3: Wild Hunt — 51.5h / 103.5h / 173h\n");
+
+    let output = run(&server, &cache, &["witcher", "--layout", "cards"]);
+    assert!(stdout(&output).starts_with("Found 2 Games, showing 1–1\n"));
+
+    let output = run(&server, &cache, &["--profile", "dlc", "--json"]);
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["count"], 1);
+    assert_eq!(result["games"][0]["name"], "Blood and Wine");
+
+    // search options don't apply to other commands
+    let output = run(&server, &cache, &["game", "10270"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = run(&server, &cache, &["witcher", "--profile", "switch"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("unknown profile \"switch\", expected one of: dlc"));
+}
+
+#[test]
+fn invalid_config_settings_are_reported() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    write_config(&cache, "size = \"lots\"\n");
+    let output = run(&server, &cache, &["witcher"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("config.toml: size: invalid value 'lots' for '--size <SIZE>'"));
+
+    write_config(&cache, "szie = 3\n");
+    let output = run(&server, &cache, &["witcher"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("config.toml: unknown option \"szie\""));
+
+    let output = run(&server, &cache, &["witcher", "--config", &cache.path().join("missing.toml").to_string_lossy()]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn config_show_prints_the_effective_settings() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    write_config(&cache, "size = 10\ncolumns = [\"name\", \"main\"]\n\n[profiles.dlc]\ndlc = true\n");
+
+    let output = run(&server, &cache, &["config", "show", "--profile", "dlc"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let settings = stdout(&output);
+    assert!(settings.starts_with("# Settings from "));
+    assert!(settings.contains("config.toml, profile dlc\n"));
+    let setting = |name: &str| settings.lines().find(|line| line.starts_with(&format!("{} = ", name))).unwrap().split_whitespace().collect::<Vec<&str>>().join(" ");
+    assert_eq!(setting("size"), "size = 10 # config file");
+    assert_eq!(setting("columns"), "columns = [\"name\", \"main\"] # config file");
+    assert_eq!(setting("dlc"), "dlc = true # profile dlc");
+    assert_eq!(setting("color"), "color = \"never\" # command line");
+    assert_eq!(setting("page"), "page = 1 # default");
+    assert!(settings.lines().any(|line| line.starts_with("# proxy =")));
+    assert!(settings.lines().any(|line| line.starts_with("# time-format =")));
+}
+
+#[test]
+fn flags_from_the_config_file_can_be_turned_off() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+    write_config(&cache, "info = true\n");
+
+    let output = run(&server, &cache, &["hades", "--layout", "cards"]);
+    assert!(stdout(&output).contains("Developer: Supergiant Games"), "{}", stdout(&output));
+
+    let output = run(&server, &cache, &["hades", "--layout", "cards", "--info=false"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!stdout(&output).contains("Developer: Supergiant Games"));
+
+    write_config(&cache, "verbose = true\n");
+    let output = run(&server, &cache, &["config", "show", "--verbose=false"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("verbose = false"));
+}
+
+#[test]
+fn saved_searches_can_be_run_listed_and_deleted() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["search", "save", "w3", "witcher", "3", "--platform", "desktop", "--sort", "main"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let saved = fs::read_to_string(cache.path().join("howlongtobeat").join("searches.toml")).unwrap();
+    assert!(saved.starts_with("# Saved searches, run them with `howlongtobeat-cli search run <name>`\n"), "{}", saved);
+    assert!(saved.contains("[w3]\nterms = \"witcher 3\"\nsort = \"main\"\n"), "{}", saved);
+    assert!(saved.contains("platform = \"pc\"\n"), "{}", saved);
+
+    let output = run(&server, &cache, &["search", "save", "dlcs", "--dlc"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let output = run(&server, &cache, &["search", "list"]);
+    assert_eq!(stdout(&output), "dlcs  --dlc\nw3    witcher 3 --sort main --platform pc\n");
+
+    let output = run(&server, &cache, &["search", "run", "w3", "--layout", "compact"]);
+    assert_eq!(stdout(&output), "The Witcher 3: Wild Hunt — 51.5h / 103.5h / 173h\n");
+    // words and filters given with `run` replace the saved ones
+    let output = run(&server, &cache, &["search", "run", "w3", "witcher", "--layout", "compact"]);
+    assert_eq!(stdout(&output).lines().count(), 2);
+    let output = run(&server, &cache, &["search", "run", "dlcs", "--json"]);
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["games"][0]["name"], "Blood and Wine");
+    let output = run(&server, &cache, &["search", "run", "dlcs", "witcher", "--no-dlc", "--json"]);
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["count"], 2);
+
+    let output = run(&server, &cache, &["search", "delete", "w3"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let output = run(&server, &cache, &["search", "run", "w3"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("unknown saved search \"w3\", expected one of: dlcs"));
+}
+
+#[test]
+fn games_nobody_played_have_no_retired_share() {
+    let server = MockServer::start();
+    let cache = TempDir::new().unwrap();
+
+    let output = run(&server, &cache, &["--dlc", "--info"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Retired: 0.0%"), "{}", stdout(&output));
+}
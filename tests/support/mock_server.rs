@@ -0,0 +1,222 @@
+//! A minimal stand-in for howlongtobeat.com, serving a fake homepage, a fake
+//! `_app` chunk with a search hash, the search API backed by
+//! `tests/fixtures/games.json` (and `unreliable_games.json`, only found by
+//! name), game pages backed by
+//! `tests/fixtures/game_pages.json`, and cover images.
+#![allow(dead_code)]
+
+use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+pub const SEARCH_HASH: &str = "5f2c9bd1a0e34c77";
+pub const APP_SCRIPT_PATH: &str = "/_next/static/chunks/pages/_app-0123456789abcdef.js";
+
+const GAMES: &str = include_str!("../fixtures/games.json");
+const UNRELIABLE_GAMES: &str = include_str!("../fixtures/unreliable_games.json");
+const GAME_PAGES: &str = include_str!("../fixtures/game_pages.json");
+
+#[derive(Clone)]
+pub struct MockOptions {
+    /// Contents of the `_app` chunk.
+    pub app_script: String,
+    /// When set, the search API answers with this status instead of results.
+    pub search_status: Option<u16>,
+    /// Only answer the first this many searches with `search_status` (all of them when zero).
+    pub search_failures: usize,
+    /// `Retry-After` header sent along with `search_status`.
+    pub retry_after: Option<String>,
+}
+
+impl Default for MockOptions {
+    fn default() -> Self {
+        MockOptions {
+            app_script: format!(r#"async function s(e){{return fetch("/api/search/".concat("{}").concat("{}"),e)}}"#, &SEARCH_HASH[..8], &SEARCH_HASH[8..]),
+            search_status: None,
+            search_failures: 0,
+            retry_after: None,
+        }
+    }
+}
+
+pub struct MockServer {
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    pub fn start() -> Self {
+        Self::start_with(MockOptions::default())
+    }
+
+    pub fn start_with(options: MockOptions) -> Self {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let log = requests.clone();
+        let searches = Arc::new(AtomicUsize::new(0));
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let listener = TcpListener::from_std(listener).unwrap();
+                loop {
+                    let Ok((stream, _)) = listener.accept().await else { continue };
+                    let options = options.clone();
+                    let log = log.clone();
+                    let searches = searches.clone();
+                    tokio::spawn(async move {
+                        let _ = handle(stream, &options, &log, &searches).await;
+                    });
+                }
+            });
+        });
+
+        MockServer { base_url, requests }
+    }
+
+    /// Request lines received so far, e.g. `GET /`.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn handle(stream: TcpStream, options: &MockOptions, log: &Mutex<Vec<String>>, searches: &AtomicUsize) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let target = parts.next().unwrap_or("").to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).await?;
+        if line.trim().is_empty() { break }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    log.lock().unwrap().push(format!("{} {}", method, target));
+
+    let path = target.split('?').next().unwrap_or("");
+    let (status, content_type, response) = route(&method, path, &body, options, searches);
+
+    let mut stream = reader.into_inner();
+    let mut head = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status, content_type, response.len()
+    );
+    if let (Some(retry_after), true) = (&options.retry_after, Some(status) == options.search_status) {
+        head.push_str(&format!("Retry-After: {}\r\n", retry_after));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&response).await?;
+    stream.shutdown().await
+}
+
+fn route(method: &str, path: &str, body: &[u8], options: &MockOptions, searches: &AtomicUsize) -> (u16, &'static str, Vec<u8>) {
+    match (method, path) {
+        ("GET", "/") => (200, "text/html", homepage().into_bytes()),
+        ("GET", APP_SCRIPT_PATH) => (200, "application/javascript", options.app_script.clone().into_bytes()),
+        ("GET", "/_next/static/chunks/main-fedcba9876543210.js") => (200, "application/javascript", b"console.log(1)".to_vec()),
+        ("POST", path) if path.starts_with("/api/search/") => {
+            if path != format!("/api/search/{}", SEARCH_HASH) {
+                return (404, "text/plain", b"Not Found".to_vec());
+            }
+            let previous_searches = searches.fetch_add(1, Ordering::SeqCst);
+            if let Some(status) = options.search_status {
+                if options.search_failures == 0 || previous_searches < options.search_failures {
+                    return (status, "text/plain", b"Error".to_vec());
+                }
+            }
+            let query: Value = serde_json::from_slice(body).unwrap_or(Value::Null);
+            (200, "application/json", search(&query).to_string().into_bytes())
+        }
+        ("GET", path) if path.starts_with("/games/") => (200, "image/png", cover()),
+        ("GET", path) if path.starts_with("/game/") => match game_page(&path["/game/".len()..]) {
+            Some(html) => (200, "text/html", html.into_bytes()),
+            None => (404, "text/plain", b"Not Found".to_vec()),
+        },
+        _ => (404, "text/plain", b"Not Found".to_vec()),
+    }
+}
+
+fn homepage() -> String {
+    format!(
+        r#"<!DOCTYPE html><html><head><script src="/_next/static/chunks/main-fedcba9876543210.js" defer></script><script src="{}" defer></script></head><body></body></html>"#,
+        APP_SCRIPT_PATH
+    )
+}
+
+fn game_page(game_id: &str) -> Option<String> {
+    let game_id: i64 = game_id.parse().ok()?;
+    let games: Vec<Value> = serde_json::from_str(GAME_PAGES).unwrap();
+    let game = games.into_iter().find(|game| game["game_id"] == game_id)?;
+    let next_data = json!({ "props": { "pageProps": { "game": { "data": { "game": [game] } } } } });
+    Some(format!(
+        r#"<!DOCTYPE html><html><body><div id="__next"></div><script id="__NEXT_DATA__" type="application/json">{}</script></body></html>"#,
+        next_data
+    ))
+}
+
+fn search(query: &Value) -> Value {
+    let terms: Vec<String> = query["searchTerms"].as_array().into_iter().flatten()
+        .filter_map(|term| term.as_str())
+        .map(|term| term.to_lowercase())
+        .collect();
+    let modifier = query["searchOptions"]["games"]["modifier"].as_str().unwrap_or("");
+    let size = query["size"].as_u64().unwrap_or(20).max(1) as usize;
+    let page = query["searchPage"].as_u64().unwrap_or(1).max(1) as usize;
+
+    let mut games: Vec<Value> = serde_json::from_str(GAMES).unwrap();
+    // games with suspicious times don't change what searches without words find
+    if terms.iter().any(|term| !term.is_empty()) {
+        games.extend(serde_json::from_str::<Vec<Value>>(UNRELIABLE_GAMES).unwrap());
+    }
+    let matching: Vec<Value> = games.into_iter()
+        .filter(|game| {
+            let name = game["game_name"].as_str().unwrap_or("").to_lowercase();
+            terms.iter().all(|term| name.contains(term))
+        })
+        .filter(|game| match modifier {
+            "only_dlc" => game["game_type"] == "dlc",
+            "hide_dlc" => game["game_type"] != "dlc",
+            _ => true,
+        })
+        .collect();
+
+    let count = matching.len();
+    let data: Vec<Value> = matching.into_iter().skip((page - 1) * size).take(size).collect();
+    json!({
+        "color": "blue",
+        "title": "",
+        "category": "games",
+        "count": count,
+        "pageCurrent": page,
+        "pageTotal": count.div_ceil(size).max(1),
+        "pageSize": size,
+        "data": data,
+        "userData": [],
+        "displayModifier": null
+    })
+}
+
+fn cover() -> Vec<u8> {
+    let image = image::RgbaImage::from_pixel(4, 6, image::Rgba([200, 60, 60, 255]));
+    let mut png = Cursor::new(Vec::new());
+    image.write_to(&mut png, image::ImageOutputFormat::Png).unwrap();
+    png.into_inner()
+}